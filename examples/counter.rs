@@ -11,6 +11,7 @@ use irisia::{
         block::BlockStyleExt,
         text::{TextStyle, TextStyleExt},
     },
+    primitive::length::PX,
     skia_safe::Color,
 };
 use irisia_widgets::layouts::{
@@ -47,7 +48,7 @@ fn app() -> impl VNode<()> {
                         format!("You clicked {count} times")
                     }).into(),
                     style: TextStyle::DEFAULT
-                        .font_size(20.0)
+                        .font_size(20 * PX)
                         .font_color(Color::WHITE),
                 }
             }
@@ -78,7 +79,7 @@ impl Component for CenterBox {
                     FlexContainerStyle::DEFAULT
                         .justify_content(JustifyContent::Center)
                         .align_items(AlignItems::Center)
-                        .background(color.copied().unwrap())
+                        .background(color.copied().unwrap().into())
                 }),
 
                 (self.children.elimate_child_data())
@@ -0,0 +1,160 @@
+use std::{cell::RefCell, rc::Rc};
+
+use irisia::{
+    Point, Result, Size, Window, WinitWindow,
+    application::PointerEvent,
+    build2, coerce_hook,
+    hook::{Signal, watcher::WatcherList},
+    model::{
+        EleModel, Model, ModelCreateCtx, VModel, VNode, component::Component, prim::PrimitiveModel,
+    },
+    prim_element::{
+        Common, Element, EmitEventArgs, EventCallback, RenderArgs, RenderTree,
+        layout::{LayoutInput, SpaceConstraint},
+    },
+    skia_safe::{Color, Paint, PaintStyle, Rect as SkRect},
+};
+
+#[irisia::main]
+async fn main() -> Result<()> {
+    Window::new(
+        WinitWindow::default_attributes().with_title("custom primitive"),
+        app,
+    )
+    .await
+    .unwrap()
+    .join()
+    .await;
+}
+
+fn app() -> impl VNode<()> {
+    let progress = Signal::state(0.0f32);
+    let advance: EventCallback = {
+        let progress = progress.clone();
+        coerce_hook!(
+            Signal::state(move |pe: PointerEvent| {
+                if let PointerEvent::PointerDown {
+                    is_current: true, ..
+                } = pe
+                {
+                    let mut w = progress.write();
+                    *w = (*w + 0.1) % 1.0;
+                }
+            })
+            .to_signal()
+        )
+    };
+
+    build2! {
+        ProgressRing {
+            progress[=]: progress.to_signal(),
+            on[=]: advance,
+        }
+    }
+}
+
+/// A ring showing the progress, from 0 to 1.
+#[derive(Default)]
+struct ProgressRing {
+    progress: Option<Signal<f32>>,
+    on: Option<EventCallback>,
+}
+
+impl Component for ProgressRing {
+    fn create(self, _: &mut WatcherList) -> impl VNode<()> + use<> {
+        ProgressRingVNode(self)
+    }
+}
+
+struct ProgressRingVNode(ProgressRing);
+
+impl VModel<()> for ProgressRingVNode {
+    type Storage = PrimitiveModel<ProgressRingModel>;
+
+    fn create(&self, ctx: &ModelCreateCtx) -> Self::Storage {
+        let model = Rc::new(RefCell::new(ProgressRingModel {
+            el: Rc::new_cyclic(|weak| {
+                RefCell::new(RenderProgressRing {
+                    progress: self.0.progress.clone(),
+                    common: Common::new(weak.clone() as _, self.0.on.clone(), ctx.element_ctx()),
+                })
+            }),
+        }));
+
+        let mut wl = WatcherList::new();
+        wl.watch_borrow_mut(
+            &model,
+            |this, _| this.el.borrow().common.request_repaint(),
+            self.0.progress.clone(),
+        );
+
+        PrimitiveModel::new(model, wl)
+    }
+
+    fn update(&self, _: &mut Self::Storage, _: &ModelCreateCtx) {
+        unreachable!("created by `ProgressRing` only, which never updates it")
+    }
+}
+
+struct ProgressRingModel {
+    el: Rc<RefCell<RenderProgressRing>>,
+}
+
+impl EleModel<()> for ProgressRingModel {
+    fn get_element(&self) -> (Element, ()) {
+        (self.el.clone(), ())
+    }
+}
+
+impl Model<()> for ProgressRingModel {
+    fn visit(&self, f: &mut dyn FnMut(Element, ())) {
+        f(self.el.clone(), ())
+    }
+}
+
+struct RenderProgressRing {
+    progress: Option<Signal<f32>>,
+    common: Common,
+}
+
+impl RenderTree for RenderProgressRing {
+    fn render(&mut self, args: RenderArgs, draw_location: Point<f32>) {
+        let size = self.common.layout().size;
+        let progress = self.progress.as_ref().map_or(0.0, |p| *p.read());
+        let stroke_width = size.width.min(size.height) * 0.1;
+
+        let oval = SkRect::from_xywh(draw_location.x, draw_location.y, size.width, size.height)
+            .with_inset((stroke_width / 2.0, stroke_width / 2.0));
+
+        let mut paint = Paint::default();
+        paint
+            .set_anti_alias(true)
+            .set_style(PaintStyle::Stroke)
+            .set_stroke_width(stroke_width)
+            .set_color(Color::LIGHT_GRAY);
+        args.canvas.draw_oval(oval, &paint);
+
+        paint.set_color(Color::BLUE);
+        args.canvas
+            .draw_arc(oval, -90.0, 360.0 * progress.clamp(0.0, 1.0), false, &paint);
+    }
+
+    fn compute_layout(&mut self, inputs: LayoutInput) -> Size<f32> {
+        let preferred = 120.0 * inputs.length_standard.width.global.dpi;
+        inputs.constraint.map(|constraint| match constraint {
+            SpaceConstraint::Exact(exact) => exact,
+            SpaceConstraint::Available(available) => preferred.min(available),
+            SpaceConstraint::MinContent | SpaceConstraint::MaxContent => preferred,
+        })
+    }
+
+    fn children_emit_event(&mut self, _: &mut EmitEventArgs) {}
+
+    fn common_mut(&mut self) -> &mut Common {
+        &mut self.common
+    }
+
+    fn common(&self) -> &Common {
+        &self.common
+    }
+}
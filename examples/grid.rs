@@ -0,0 +1,88 @@
+use irisia::{
+    Result, Window, WinitWindow, build2,
+    model::{VNode, prim::Block},
+    prim_element::block::{BlockStyle, BlockStyleExt},
+    primitive::{
+        length::{PCT, PX},
+        size::Size,
+    },
+    skia_safe::Color,
+};
+use irisia_widgets::layouts::{
+    GridContainerStyle, GridContainerStyleExt, GridItemStyle, GridItemStyleExt, GridPlacement,
+    GridTrack, TrackSize, base_style::ChildStyleExt, grid::Grid,
+};
+
+#[irisia::main]
+async fn main() -> Result<()> {
+    Window::new(
+        WinitWindow::default_attributes().with_title("irisia grid"),
+        app,
+    )
+    .await
+    .unwrap()
+    .join()
+    .await;
+}
+
+fn app() -> impl VNode<()> {
+    build2! {
+        Grid {
+            style: GridContainerStyle::DEFAULT
+                .grid_template_columns(vec![
+                    GridTrack::from(120 * PX),
+                    GridTrack::repeat(2, [TrackSize::fr(1.0)]),
+                ])
+                .grid_template_rows(vec![
+                    GridTrack::from(60 * PX),
+                    TrackSize::fr(1.0).into(),
+                    GridTrack::from(40 * PX),
+                ])
+                .gap(Size::all(8 * PX))
+                .background(Color::WHITE.into()),
+
+            // header
+            Block::<()> {
+                style: BlockStyle::DEFAULT
+                    .background(Color::DARK_GRAY.into()),
+
+                super: GridItemStyle::DEFAULT
+                    .grid_column_start(GridPlacement::Line(1))
+                    .grid_column_end(GridPlacement::Line(-1)),
+            }
+
+            // sidebar
+            Block::<()> {
+                style: BlockStyle::DEFAULT
+                    .background(Color::GRAY.into()),
+
+                super: GridItemStyle::DEFAULT
+                    .grid_row_start(GridPlacement::Line(2))
+                    .grid_row_end(GridPlacement::Span(2)),
+            }
+
+            Block::<()> {
+                style: BlockStyle::DEFAULT
+                    .background(Color::RED.into()),
+
+                super: GridItemStyle::DEFAULT,
+            }
+
+            Block::<()> {
+                style: BlockStyle::DEFAULT
+                    .background(Color::BLUE.into()),
+
+                super: GridItemStyle::DEFAULT
+                    .height(0.5 * PCT),
+            }
+
+            Block::<()> {
+                style: BlockStyle::DEFAULT
+                    .background(Color::GREEN.into()),
+
+                super: GridItemStyle::DEFAULT
+                    .grid_column_end(GridPlacement::Span(2)),
+            }
+        }
+    }
+}
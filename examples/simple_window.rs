@@ -62,13 +62,16 @@ fn app() -> impl VNode<()> {
                     style: BlockStyle {
                         // width: 0.5 * VMIN,
                         // height: 0.5 * VMIN,
-                        background: if is_red { Color::RED } else { Color::BLUE },
+                        background: if is_red { Color::RED } else { Color::BLUE }.into(),
                         border_radius: Corner::all(50.0),
                         border_width: Rect {
                             left: 10 * PX,
                             ..Default::default()
                         },
-                        border_color: Color::GRAY,
+                        border_color: Rect {
+                            left: Color::GRAY,
+                            ..Rect::all(Color::DARK_GRAY)
+                        },
                         ..BlockStyle::DEFAULT
                     },
                     super: AvgProps {
@@ -101,7 +104,7 @@ fn app() -> impl VNode<()> {
                 // },
                 text[=]: coerce_hook!(text),
                 style: TextStyle {
-                    font_size: 40.0,
+                    font_size: 40 * PX,
                     font_color: Color::MAGENTA,
                 },
 
@@ -115,7 +118,7 @@ fn app() -> impl VNode<()> {
                     style: BlockStyle {
                         // width: 1 * PCT,
                         // height: 10 * PX,
-                        background: Color::BLACK,
+                        background: Color::BLACK.into(),
                         border_radius: Corner::all(50.0),
                         ..BlockStyle::DEFAULT
                     },
@@ -128,7 +131,7 @@ fn app() -> impl VNode<()> {
                 _ => Text {
                     style: TextStyle {
                         font_color: Color::BLACK,
-                        font_size: 20.0,
+                        font_size: 20 * PX,
                     },
                     text: "match表达式".to_string(),
                     // [foo]: 4,
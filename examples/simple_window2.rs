@@ -1,7 +1,8 @@
-use std::cell::Cell;
+use std::{cell::Cell, time::Duration};
 
 use irisia::{
-    Result, Window, WinitWindow,
+    Point, Result, Window, WinitWindow,
+    animation::Transition,
     application::PointerEvent,
     build2, coerce_hook,
     hook::Signal,
@@ -12,7 +13,10 @@ use irisia::{
         prim::{Block, Text},
     },
     prim_element::{
-        block::{BlockLayout, BlockStyle, BlockStyleExt, layout::LayoutChildren},
+        block::{
+            Background, BlockLayout, BlockStyle, BlockStyleExt, BorderStyle, BoxShadow, ColorStop,
+            layout::LayoutChildren,
+        },
         layout::SpaceConstraint,
         text::TextStyle,
     },
@@ -73,20 +77,25 @@ fn app() -> impl VNode<()> {
 
     build2! {
         Flex {
-            style[=]: Signal::memo(flex_direction.to_signal(), |&flex_direction| {
-                FlexContainerStyle::DEFAULT
-                    .flex_direction(flex_direction)
-                    .justify_content(JustifyContent::Center)
-                    .align_items(AlignItems::Center)
-                    .align_content(AlignContent::Center)
-                    .background(Color::GRAY)
-            }),
+            style[=]: Transition::new(Duration::from_millis(300)).apply(
+                Signal::memo(flex_direction.to_signal(), |&flex_direction| {
+                    FlexContainerStyle::DEFAULT
+                        .flex_direction(flex_direction)
+                        .justify_content(JustifyContent::Center)
+                        .align_items(AlignItems::Center)
+                        .align_content(AlignContent::Center)
+                        .background(match flex_direction {
+                            FlexDirection::Row => Color::GRAY.into(),
+                            _ => Color::DARK_GRAY.into(),
+                        })
+                })
+            ),
 
             (extra_blocks.to_signal())
 
             Block::<()> {
                 style: BlockStyle::DEFAULT
-                    .background(Color::RED),
+                    .background(Color::RED.into()),
 
                 super: FlexItemStyle::DEFAULT
                     .width(60 * PX)
@@ -97,7 +106,7 @@ fn app() -> impl VNode<()> {
                 on: on_click,
 
                 style: BlockStyle::DEFAULT
-                    .background(Color::BLACK),
+                    .background(Color::BLACK.into()),
 
                 super: FlexItemStyle::DEFAULT
                     .width(0.2 * PCT)
@@ -106,7 +115,19 @@ fn app() -> impl VNode<()> {
 
             Block::<()> {
                 style: BlockStyle::DEFAULT
-                    .background(Color::BLUE),
+                    .background(Background::LinearGradient {
+                        angle: 135.0,
+                        stops: vec![
+                            ColorStop { color: Color::BLUE, offset: 0.0 },
+                            ColorStop { color: Color::CYAN, offset: 1.0 },
+                        ],
+                    })
+                    .box_shadow(vec![BoxShadow {
+                        offset: Point { x: 4 * PX, y: 4 * PX },
+                        blur: 8 * PX,
+                        color: Color::from_argb(0x60, 0, 0, 0),
+                        ..BoxShadow::DEFAULT
+                    }]),
 
                 super: FlexItemStyle::DEFAULT
                     .width(0.2 * VW)
@@ -120,9 +141,10 @@ fn create_block() -> impl VNode<FlexItemStyle> {
     build2! {
         Block::<()> {
             style: BlockStyle::DEFAULT
-                .background(Color::RED)
+                .background(Color::RED.into())
                 .border_width(Rect::all(1 * PX))
-                .border_color(Color::BLACK),
+                .border_color(Rect::all(Color::BLACK))
+                .border_style(Rect::all(BorderStyle::Dashed)),
 
             super: FlexItemStyle::DEFAULT
                 .width(60 * PX)
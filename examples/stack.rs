@@ -0,0 +1,59 @@
+use irisia::{
+    Point, Result, Window, WinitWindow, build2,
+    model::{VNode, prim::Block},
+    prim_element::block::{BlockStyle, BlockStyleExt},
+    primitive::length::PX,
+    skia_safe::Color,
+};
+use irisia_widgets::layouts::{
+    StackAnchor, StackItemStyle, StackItemStyleExt, base_style::ChildStyleExt, stack::Stack,
+};
+
+#[irisia::main]
+async fn main() -> Result<()> {
+    Window::new(
+        WinitWindow::default_attributes().with_title("irisia stack"),
+        app,
+    )
+    .await
+    .unwrap()
+    .join()
+    .await;
+}
+
+fn app() -> impl VNode<()> {
+    build2! {
+        Stack {
+            // backdrop
+            Block::<()> {
+                style: BlockStyle::DEFAULT
+                    .background(Color::LIGHT_GRAY.into()),
+
+                super: StackItemStyle::DEFAULT
+                    .stretch(Point::all(true)),
+            }
+
+            // avatar with a badge on its corner
+            Stack {
+                style: BlockStyle::DEFAULT
+                    .background(Color::DARK_GRAY.into()),
+
+                super: StackItemStyle::DEFAULT
+                    .anchor(StackAnchor::Center)
+                    .width(120 * PX)
+                    .height(120 * PX),
+
+                Block::<()> {
+                    style: BlockStyle::DEFAULT
+                        .background(Color::RED.into()),
+
+                    super: StackItemStyle::DEFAULT
+                        .anchor(StackAnchor::TopEnd)
+                        .offset(Point { x: 8 * PX, y: -8 * PX })
+                        .width(24 * PX)
+                        .height(24 * PX),
+                }
+            }
+        }
+    }
+}
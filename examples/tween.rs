@@ -0,0 +1,88 @@
+use std::{cell::Cell, time::Duration};
+
+use irisia::{
+    Result, Window, WinitWindow,
+    animation::{Animation, Easing, animate},
+    application::PointerEvent,
+    build2, coerce_hook,
+    hook::Signal,
+    model::{VNode, prim::Block},
+    prim_element::{
+        EventCallback,
+        block::{BlockStyle, BlockStyleExt},
+    },
+    primitive::{Corner, length::PX},
+    skia_safe::Color,
+};
+use irisia_widgets::layouts::{
+    StackAnchor, StackItemStyle, StackItemStyleExt, base_style::ChildStyleExt, stack::Stack,
+};
+
+#[irisia::main]
+async fn main() -> Result<()> {
+    Window::new(
+        WinitWindow::default_attributes().with_title("irisia tween"),
+        app,
+    )
+    .await
+    .unwrap()
+    .join()
+    .await;
+}
+
+fn app() -> impl VNode<()> {
+    let square = BlockStyle::DEFAULT.background(Color::BLUE.into());
+    let circle = BlockStyle::DEFAULT
+        .background(Color::RED.into())
+        .border_radius(Corner::all(80.0));
+
+    let style = Signal::state(square.clone());
+    let toggle: EventCallback = {
+        let style = style.clone();
+        let is_circle = Cell::new(false);
+        let running: Cell<Option<Animation>> = Cell::new(None);
+        coerce_hook!(
+            Signal::state(move |pe: PointerEvent| {
+                if let PointerEvent::PointerDown {
+                    is_current: true, ..
+                } = pe
+                {
+                    if let Some(prev) = running.take() {
+                        prev.cancel();
+                    }
+                    is_circle.set(!is_circle.get());
+                    let to = if is_circle.get() {
+                        circle.clone()
+                    } else {
+                        square.clone()
+                    };
+
+                    // from where it is, so clicking halfway turns it back smoothly
+                    let from = style.read().clone();
+                    running.set(Some(animate(
+                        &style,
+                        from,
+                        to,
+                        Duration::from_millis(400),
+                        Easing::EaseInOut,
+                    )));
+                }
+            })
+            .to_signal()
+        )
+    };
+
+    build2! {
+        Stack {
+            Block::<()> {
+                style[=]: style.to_signal(),
+                on[=]: toggle,
+
+                super: StackItemStyle::DEFAULT
+                    .anchor(StackAnchor::Center)
+                    .width(160 * PX)
+                    .height(160 * PX),
+            }
+        }
+    }
+}
@@ -11,7 +11,9 @@ use crate::generics_unbracketed::split_for_impl_unbracketed;
 
 pub struct ImplStyle {
     trait_name: Ident,
+    interpolate: bool,
     extends: Option<(Ident, Type)>,
+    discrete_fields: Vec<Ident>,
     struct_def: ItemStruct,
 }
 
@@ -19,15 +21,34 @@ impl ImplStyle {
     pub fn parse(attr: TokenStream1, struct_tokens: TokenStream1) -> Result<Self> {
         let ParseArgs {
             args: (trait_name,),
+            meta: interpolate,
             ..
-        } = (|input: ParseStream| ParseArgs::new().args::<(Ident,)>().parse(input)).parse(attr)?;
+        } = (|input: ParseStream| {
+            ParseArgs::new()
+                .args::<(Ident,)>()
+                .meta(("interpolate", path_only()))
+                .parse(input)
+        })
+        .parse(attr)?;
 
         let mut struct_def = ItemStruct::parse.parse(struct_tokens)?;
-        let extends = find_extend_field(&mut struct_def)?;
+        let FieldAttrs {
+            extends,
+            discrete_fields,
+        } = parse_field_attrs(&mut struct_def)?;
+
+        if !interpolate && !discrete_fields.is_empty() {
+            return Err(Error::new_spanned(
+                &discrete_fields[0],
+                "`discrete` fields require `interpolate` on the struct",
+            ));
+        }
 
         Ok(Self {
             trait_name,
+            interpolate,
             extends,
+            discrete_fields,
             struct_def,
         })
     }
@@ -37,6 +58,7 @@ impl ImplStyle {
             trait_name,
             extends,
             struct_def,
+            ..
         } = self;
 
         let ItemStruct {
@@ -46,6 +68,8 @@ impl ImplStyle {
             ..
         } = struct_def;
 
+        let interpolate_tokens = self.interpolate_tokens();
+
         let use_style_trait = quote! { irisia::model::UseStyle };
         let (impl_g, type_g, where_predicates) = split_for_impl_unbracketed(generics);
 
@@ -93,32 +117,91 @@ impl ImplStyle {
             {}
 
             #extend_tokens
+            #interpolate_tokens
         }
     }
+
+    fn interpolate_tokens(&self) -> Option<TokenStream> {
+        if !self.interpolate {
+            return None;
+        }
+
+        let ItemStruct {
+            generics,
+            ident: struct_name,
+            fields,
+            ..
+        } = &self.struct_def;
+
+        let interpolate_trait = quote! { irisia::animation::Interpolate };
+        let (impl_g, type_g, where_predicates) = split_for_impl_unbracketed(generics);
+
+        let field_values = fields.iter().map(|field| {
+            let ident = field.ident.as_ref().unwrap();
+            if self.discrete_fields.contains(ident) {
+                quote! {
+                    #ident: irisia::animation::discrete(&self.#ident, &to.#ident, t)
+                }
+            } else {
+                quote! {
+                    #ident: #interpolate_trait::interpolate(&self.#ident, &to.#ident, t)
+                }
+            }
+        });
+
+        Some(quote! {
+            impl<#impl_g> #interpolate_trait for #struct_name<#type_g>
+            where
+                #where_predicates
+            {
+                fn interpolate(&self, to: &Self, t: f32) -> Self {
+                    Self {
+                        #(#field_values,)*
+                    }
+                }
+            }
+        })
+    }
+}
+
+struct FieldAttrs {
+    extends: Option<(Ident, Type)>,
+    discrete_fields: Vec<Ident>,
 }
 
-fn find_extend_field(item: &mut ItemStruct) -> Result<Option<(Ident, Type)>> {
+fn parse_field_attrs(item: &mut ItemStruct) -> Result<FieldAttrs> {
     let fields = match &mut item.fields {
         fields @ Fields::Unnamed(_) => {
             return Err(Error::new_spanned(fields, "fields cannot be unnamed"));
         }
-        Fields::Unit => return Ok(None),
+        Fields::Unit => {
+            return Ok(FieldAttrs {
+                extends: None,
+                discrete_fields: Vec::new(),
+            });
+        }
         Fields::Named(fields) => fields,
     };
 
     let mut extends = None;
+    let mut discrete_fields = Vec::new();
+
     for field in fields.named.iter_mut() {
         let Some(style_attr) = find_attr::only(&field.attrs, "style")? else {
             continue;
         };
 
         let ParseArgs {
-            meta: should_extend,
+            meta: (should_extend, is_discrete),
             ..
         } = ParseArgs::new()
-            .meta(("extend", path_only()))
+            .meta((("extend", path_only()), ("discrete", path_only())))
             .parse_attr(style_attr)?;
 
+        if is_discrete {
+            discrete_fields.push(field.ident.clone().unwrap());
+        }
+
         if should_extend {
             if extends.is_some() {
                 return Err(Error::new_spanned(
@@ -133,5 +216,8 @@ fn find_extend_field(item: &mut ItemStruct) -> Result<Option<(Ident, Type)>> {
         field.attrs.retain(|attr| !attr.path().is_ident("style"));
     }
 
-    Ok(extends)
+    Ok(FieldAttrs {
+        extends,
+        discrete_fields,
+    })
 }
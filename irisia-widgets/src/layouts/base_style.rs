@@ -2,10 +2,12 @@ use irisia::{
     primitive::{length::LengthStandard, Length},
     style, Point, Rect, Size,
 };
-use taffy::{Overflow, Position};
+use taffy::Overflow;
 
 use crate::layouts::{point_to_taffy, rect_to_taffy, size_to_taffy, ResolvedStyle};
 
+/// Layout style of a child given by its parent. The child positions itself,
+/// see `BlockStyle::position`, the layouters skip it if out of the flow.
 #[style(ChildStyleExt)]
 #[derive(Clone, Copy, PartialEq)]
 pub struct ChildStyle {
@@ -16,8 +18,8 @@ pub struct ChildStyle {
     pub max_width: Length,
     pub max_height: Length,
     pub overflow: Point<Overflow>,
-    pub position: Position,
-    pub inset: Rect<Length>,
+    /// `Length::Auto` takes the free space, so it can be used to center a child.
+    pub margin: Rect<Length>,
     pub aspect_ratio: Option<f32>,
 }
 
@@ -30,8 +32,7 @@ impl ChildStyle {
         max_width: Length::Auto,
         max_height: Length::Auto,
         overflow: Point::all(Overflow::Hidden),
-        position: Position::Relative,
-        inset: Rect::all(Length::Auto),
+        margin: Rect::all(Length::ZERO),
         aspect_ratio: None,
     };
 }
@@ -55,23 +56,8 @@ impl taffy::CoreStyle for ResolvedStyle<'_, &ChildStyle> {
         point_to_taffy(self.style.overflow)
     }
 
-    fn position(&self) -> Position {
-        self.style.position
-    }
-
-    fn inset(&self) -> taffy::Rect<taffy::LengthPercentageAuto> {
-        rect_to_taffy(
-            self.style
-                .inset
-                .split_into_hv_components()
-                .map_with(self.length_standard.to_point(), |axis, ls| {
-                    axis.map(|x| match ls.resolve(x) {
-                        Some(value) => taffy::LengthPercentageAuto::length(value),
-                        None => taffy::LengthPercentageAuto::auto(),
-                    })
-                })
-                .merge_hv_components(),
-        )
+    fn margin(&self) -> taffy::Rect<taffy::LengthPercentageAuto> {
+        rect_to_length_percentage_auto(self.style.margin, self.length_standard)
     }
 
     fn size(&self) -> taffy::Size<taffy::Dimension> {
@@ -111,3 +97,19 @@ fn width_height_to_style_size(
         }),
     )
 }
+
+fn rect_to_length_percentage_auto(
+    rect: Rect<Length>,
+    ls: Size<&LengthStandard>,
+) -> taffy::Rect<taffy::LengthPercentageAuto> {
+    rect_to_taffy(
+        rect.split_into_hv_components()
+            .map_with(ls.to_point(), |axis, ls| {
+                axis.map(|x| match ls.resolve(x) {
+                    Some(value) => taffy::LengthPercentageAuto::length(value),
+                    None => taffy::LengthPercentageAuto::auto(),
+                })
+            })
+            .merge_hv_components(),
+    )
+}
@@ -0,0 +1,77 @@
+use irisia::{
+    prim_element::{
+        block::{layout::LayoutChildren, BlockLayout},
+        layout::SpaceConstraint,
+    },
+    Size,
+};
+use taffy::{LayoutBlockContainer, LayoutPartialTree};
+
+use crate::layouts::{
+    base_style::ChildStyle, local_layout_input_to_taffy, mirror_children, size_to_local, MyTree,
+    ResolvedStyle, PARENT_NODE_ID,
+};
+
+#[derive(Clone, Copy)]
+pub(super) struct ColumnBlockLayout;
+
+impl BlockLayout<ChildStyle> for ColumnBlockLayout {
+    fn compute_layout(
+        &self,
+        children: LayoutChildren<ChildStyle>,
+        constraints: Size<SpaceConstraint>,
+    ) -> Size<f32> {
+        let parent_size = children.parent_size().map(Some);
+        let mut tree = MyTree {
+            container_style: self,
+            children,
+        };
+
+        let size = size_to_local(
+            taffy::compute_block_layout(
+                &mut tree,
+                PARENT_NODE_ID,
+                local_layout_input_to_taffy(constraints, parent_size),
+            )
+            .size,
+        );
+
+        mirror_children(&tree.children, size.width);
+        size
+    }
+}
+
+impl<'tree> LayoutBlockContainer for MyTree<'tree, ColumnBlockLayout, ChildStyle> {
+    type BlockContainerStyle<'a>
+        = ResolvedStyle<'a, &'a ColumnBlockLayout>
+    where
+        Self: 'a;
+
+    type BlockItemStyle<'a>
+        = ResolvedStyle<'a, &'a ChildStyle>
+    where
+        Self: 'a;
+
+    fn get_block_container_style(&self, node_id: taffy::NodeId) -> Self::BlockContainerStyle<'_> {
+        self.get_core_container_style(node_id)
+    }
+
+    fn get_block_child_style(&self, child_node_id: taffy::NodeId) -> Self::BlockItemStyle<'_> {
+        ResolvedStyle {
+            length_standard: self.children.length_standard().as_ref(),
+            style: self.children.get(child_node_id.into()).data(),
+        }
+    }
+}
+
+impl taffy::CoreStyle for ResolvedStyle<'_, &ColumnBlockLayout> {
+    // everything use default
+}
+
+impl taffy::BlockContainerStyle for ResolvedStyle<'_, &ColumnBlockLayout> {
+    // everything use default
+}
+
+impl taffy::BlockItemStyle for ResolvedStyle<'_, &ChildStyle> {
+    // everything use default
+}
@@ -0,0 +1,39 @@
+use irisia::{
+    build2,
+    hook::Signal,
+    model::{component::Component, control_flow::CommonVModel, prim::Block},
+    prim_element::block::BlockStyle,
+};
+
+use crate::layouts::{base_style::ChildStyle, column::implement::ColumnBlockLayout};
+
+mod implement;
+
+/// Stacks children vertically like blocks in a css document. Children fill
+/// the width unless they have one, and vertical margins of adjacent children
+/// collapse into the larger one. Use `Length::Auto` horizontal margins to
+/// center a child.
+#[derive(Default)]
+pub struct Column {
+    pub style: Option<Signal<BlockStyle>>,
+    pub children: Option<Signal<dyn CommonVModel<ChildStyle>>>,
+}
+
+impl Component for Column {
+    fn create(
+        self,
+        _watcher_list: &mut irisia::hook::watcher::WatcherList,
+    ) -> impl irisia::model::VNode<()> + use<> {
+        Signal::memo_ncmp(self.style, move |style| {
+            let style = style.cloned().unwrap_or_default();
+            build2! {
+                Block {
+                    display: ColumnBlockLayout,
+                    style: style,
+
+                    (self.children.clone())
+                }
+            }
+        })
+    }
+}
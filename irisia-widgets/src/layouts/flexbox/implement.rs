@@ -1,5 +1,8 @@
 use irisia::{
-    prim_element::block::{layout::LayoutChildren, BlockLayout},
+    prim_element::{
+        block::{layout::LayoutChildren, BlockLayout},
+        layout::Direction,
+    },
     Size,
 };
 use taffy::{
@@ -42,7 +45,7 @@ impl BlockLayout<FlexItemStyle> for FlexBlockLayout {
 
 impl<'tree> LayoutFlexboxContainer for MyTree<'tree, FlexContainerStyle, FlexItemStyle> {
     type FlexboxContainerStyle<'a>
-        = ResolvedStyle<'a, &'a FlexContainerStyle>
+        = ResolvedStyle<'a, DirectedFlexContainerStyle<'a>>
     where
         Self: 'a;
 
@@ -73,7 +76,10 @@ impl<'tree> LayoutFlexboxContainer for MyTree<'tree, FlexContainerStyle, FlexIte
         assert_parent_node(node_id);
         ResolvedStyle {
             length_standard: self.children.length_standard().as_ref(),
-            style: &self.container_style,
+            style: DirectedFlexContainerStyle {
+                style: self.container_style,
+                direction: self.children.direction(),
+            },
         }
     }
 }
@@ -82,31 +88,46 @@ impl taffy::CoreStyle for ResolvedStyle<'_, &FlexContainerStyle> {
     // everything use default
 }
 
-impl taffy::FlexboxContainerStyle for ResolvedStyle<'_, &FlexContainerStyle> {
+// rows are reversed under rtl, the items keep their sizes unlike mirroring them
+pub(crate) struct DirectedFlexContainerStyle<'a> {
+    style: &'a FlexContainerStyle,
+    direction: Direction,
+}
+
+impl taffy::CoreStyle for ResolvedStyle<'_, DirectedFlexContainerStyle<'_>> {
+    // everything use default
+}
+
+impl taffy::FlexboxContainerStyle for ResolvedStyle<'_, DirectedFlexContainerStyle<'_>> {
     fn align_content(&self) -> Option<AlignContent> {
-        Some(self.style.align_content)
+        Some(self.style.style.align_content)
     }
 
     fn align_items(&self) -> Option<AlignItems> {
-        self.style.align_items.to_taffy()
+        self.style.style.align_items.to_taffy()
     }
 
     fn flex_direction(&self) -> FlexDirection {
-        self.style.flex_direction
+        match (self.style.style.flex_direction, self.style.direction) {
+            (FlexDirection::Row, Direction::Rtl) => FlexDirection::RowReverse,
+            (FlexDirection::RowReverse, Direction::Rtl) => FlexDirection::Row,
+            (flex_direction, _) => flex_direction,
+        }
     }
 
     fn flex_wrap(&self) -> FlexWrap {
-        self.style.flex_wrap
+        self.style.style.flex_wrap
     }
 
     fn gap(&self) -> taffy::Size<taffy::LengthPercentage> {
-        size_to_taffy(self.style.gap.map_with(self.length_standard, |x, ls| {
+        let style = self.style.style;
+        size_to_taffy(style.gap.map_with(self.length_standard, |x, ls| {
             taffy::LengthPercentage::length(ls.resolve(x).unwrap_or(0.0))
         }))
     }
 
     fn justify_content(&self) -> Option<JustifyContent> {
-        Some(self.style.justify_content)
+        Some(self.style.style.justify_content)
     }
 }
 
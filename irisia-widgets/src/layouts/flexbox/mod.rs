@@ -29,7 +29,7 @@ impl Component for Flex {
             build2! {
                 Block {
                     display: FlexBlockLayout {
-                        container_style: style,
+                        container_style: style.clone(),
                     },
                     style: style.base,
 
@@ -41,14 +41,19 @@ impl Component for Flex {
     }
 }
 
-#[style(FlexContainerStyleExt)]
-#[derive(Clone, Copy, PartialEq)]
+#[style(FlexContainerStyleExt, interpolate)]
+#[derive(Clone, PartialEq)]
 pub struct FlexContainerStyle {
+    #[style(discrete)]
     pub flex_direction: FlexDirection,
+    #[style(discrete)]
     pub flex_wrap: FlexWrap,
     pub gap: Size<Length>,
+    #[style(discrete)]
     pub align_content: AlignContent,
+    #[style(discrete)]
     pub align_items: AlignItems,
+    #[style(discrete)]
     pub justify_content: JustifyContent,
 
     #[style(extend)]
@@ -114,7 +119,7 @@ pub enum AlignItems {
 }
 
 impl AlignItems {
-    fn to_taffy(self) -> Option<taffy::AlignItems> {
+    pub(crate) fn to_taffy(self) -> Option<taffy::AlignItems> {
         macro_rules! map {
             ($($Var:ident,)*) => {
                 match self {
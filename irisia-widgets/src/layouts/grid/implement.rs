@@ -0,0 +1,232 @@
+use irisia::{
+    prim_element::{
+        block::{layout::LayoutChildren, BlockLayout},
+        layout::SpaceConstraint,
+    },
+    primitive::length::LengthStandard,
+    Size,
+};
+use taffy::{
+    style_helpers::{line, span},
+    AlignContent, AlignItems, AlignSelf, GridAutoFlow, JustifyContent, LayoutGridContainer,
+    LayoutPartialTree, MaxTrackSizingFunction, MinTrackSizingFunction,
+    NonRepeatedTrackSizingFunction, TrackSizingFunction,
+};
+
+use crate::layouts::{
+    local_layout_input_to_taffy, mirror_children, size_to_local, size_to_taffy, CoreStyleMigration,
+    MyTree, ResolvedStyle, PARENT_NODE_ID,
+};
+
+use super::{
+    GridContainerStyle, GridItemStyle, GridPlacement, GridTrack, MaxTrackSize, MinTrackSize,
+    TrackSize,
+};
+
+#[derive(Clone)]
+pub(super) struct GridBlockLayout {
+    pub container_style: GridContainerStyle,
+}
+
+impl BlockLayout<GridItemStyle> for GridBlockLayout {
+    fn compute_layout(
+        &self,
+        children: LayoutChildren<GridItemStyle>,
+        constraints: Size<SpaceConstraint>,
+    ) -> Size<f32> {
+        let parent_size = children.parent_size().map(Some);
+        let mut tree = MyTree {
+            container_style: &self.container_style,
+            children,
+        };
+
+        let size = size_to_local(
+            taffy::compute_grid_layout(
+                &mut tree,
+                PARENT_NODE_ID,
+                local_layout_input_to_taffy(constraints, parent_size),
+            )
+            .size,
+        );
+
+        mirror_children(&tree.children, size.width);
+        size
+    }
+}
+
+impl<'tree> LayoutGridContainer for MyTree<'tree, GridContainerStyle, GridItemStyle> {
+    type GridContainerStyle<'a>
+        = ResolvedStyle<'a, &'a GridContainerStyle>
+    where
+        Self: 'a;
+
+    type GridItemStyle<'a>
+        = ResolvedStyle<'a, &'a GridItemStyle>
+    where
+        Self: 'a;
+
+    fn get_grid_container_style(&self, node_id: taffy::NodeId) -> Self::GridContainerStyle<'_> {
+        self.get_core_container_style(node_id)
+    }
+
+    fn get_grid_child_style(&self, child_node_id: taffy::NodeId) -> Self::GridItemStyle<'_> {
+        ResolvedStyle {
+            length_standard: self.children.length_standard().as_ref(),
+            style: self.children.get(child_node_id.into()).data(),
+        }
+    }
+}
+
+impl taffy::CoreStyle for ResolvedStyle<'_, &GridContainerStyle> {
+    // everything use default
+}
+
+impl taffy::GridContainerStyle for ResolvedStyle<'_, &GridContainerStyle> {
+    type TemplateTrackList<'a>
+        = Vec<TrackSizingFunction>
+    where
+        Self: 'a;
+
+    type AutoTrackList<'a>
+        = Vec<NonRepeatedTrackSizingFunction>
+    where
+        Self: 'a;
+
+    fn grid_template_rows(&self) -> Self::TemplateTrackList<'_> {
+        resolve_template(&self.style.grid_template_rows, self.length_standard.height)
+    }
+
+    fn grid_template_columns(&self) -> Self::TemplateTrackList<'_> {
+        resolve_template(
+            &self.style.grid_template_columns,
+            self.length_standard.width,
+        )
+    }
+
+    fn grid_auto_rows(&self) -> Self::AutoTrackList<'_> {
+        resolve_tracks(&self.style.grid_auto_rows, self.length_standard.height)
+    }
+
+    fn grid_auto_columns(&self) -> Self::AutoTrackList<'_> {
+        resolve_tracks(&self.style.grid_auto_columns, self.length_standard.width)
+    }
+
+    fn grid_auto_flow(&self) -> GridAutoFlow {
+        self.style.grid_auto_flow
+    }
+
+    fn gap(&self) -> taffy::Size<taffy::LengthPercentage> {
+        size_to_taffy(self.style.gap.map_with(self.length_standard, |x, ls| {
+            taffy::LengthPercentage::length(ls.resolve(x).unwrap_or(0.0))
+        }))
+    }
+
+    fn align_content(&self) -> Option<AlignContent> {
+        Some(self.style.align_content)
+    }
+
+    fn justify_content(&self) -> Option<JustifyContent> {
+        Some(self.style.justify_content)
+    }
+
+    fn align_items(&self) -> Option<AlignItems> {
+        self.style.align_items.to_taffy()
+    }
+
+    fn justify_items(&self) -> Option<AlignItems> {
+        self.style.justify_items.to_taffy()
+    }
+}
+
+impl<'a, 'b> CoreStyleMigration for ResolvedStyle<'a, &'b GridItemStyle> {
+    fn as_core_style(&self) -> impl taffy::CoreStyle + use<'_, 'a, 'b> {
+        self.wrap(&self.style.base)
+    }
+}
+
+impl taffy::GridItemStyle for ResolvedStyle<'_, &GridItemStyle> {
+    fn grid_row(&self) -> taffy::Line<taffy::GridPlacement> {
+        taffy::Line {
+            start: self.style.grid_row_start.to_taffy(),
+            end: self.style.grid_row_end.to_taffy(),
+        }
+    }
+
+    fn grid_column(&self) -> taffy::Line<taffy::GridPlacement> {
+        taffy::Line {
+            start: self.style.grid_column_start.to_taffy(),
+            end: self.style.grid_column_end.to_taffy(),
+        }
+    }
+
+    fn align_self(&self) -> Option<AlignSelf> {
+        self.style.align_self.to_taffy()
+    }
+
+    fn justify_self(&self) -> Option<AlignSelf> {
+        self.style.justify_self.to_taffy()
+    }
+}
+
+impl GridPlacement {
+    fn to_taffy(self) -> taffy::GridPlacement {
+        match self {
+            Self::Auto => taffy::GridPlacement::Auto,
+            Self::Line(index) => line(index),
+            Self::Span(count) => span(count),
+        }
+    }
+}
+
+fn resolve_template(tracks: &[GridTrack], ls: &LengthStandard) -> Vec<TrackSizingFunction> {
+    tracks
+        .iter()
+        .map(|track| match track {
+            GridTrack::Single(size) => TrackSizingFunction::Single(size.to_taffy(ls)),
+            GridTrack::Repeat(repetition, sizes) => {
+                TrackSizingFunction::Repeat(*repetition, resolve_tracks(sizes, ls))
+            }
+        })
+        .collect()
+}
+
+fn resolve_tracks(
+    tracks: &[TrackSize],
+    ls: &LengthStandard,
+) -> Vec<NonRepeatedTrackSizingFunction> {
+    tracks.iter().map(|size| size.to_taffy(ls)).collect()
+}
+
+impl TrackSize {
+    fn to_taffy(self, ls: &LengthStandard) -> NonRepeatedTrackSizingFunction {
+        let fixed = |length| ls.resolve(length).map(taffy::LengthPercentage::length);
+
+        let min = match self.min {
+            MinTrackSize::Length(length) => match fixed(length) {
+                Some(fixed) => MinTrackSizingFunction::Fixed(fixed),
+                None => MinTrackSizingFunction::Auto,
+            },
+            MinTrackSize::Auto => MinTrackSizingFunction::Auto,
+            MinTrackSize::MinContent => MinTrackSizingFunction::MinContent,
+            MinTrackSize::MaxContent => MinTrackSizingFunction::MaxContent,
+        };
+
+        let max = match self.max {
+            MaxTrackSize::Length(length) => match fixed(length) {
+                Some(fixed) => MaxTrackSizingFunction::Fixed(fixed),
+                None => MaxTrackSizingFunction::Auto,
+            },
+            // an auto limit doesn't limit anything
+            MaxTrackSize::FitContent(length) => match fixed(length) {
+                Some(fixed) => MaxTrackSizingFunction::FitContent(fixed),
+                None => MaxTrackSizingFunction::MaxContent,
+            },
+            MaxTrackSize::Auto => MaxTrackSizingFunction::Auto,
+            MaxTrackSize::MinContent => MaxTrackSizingFunction::MinContent,
+            MaxTrackSize::MaxContent => MaxTrackSizingFunction::MaxContent,
+            MaxTrackSize::Fr(fr) => MaxTrackSizingFunction::Fraction(fr),
+        };
+
+        NonRepeatedTrackSizingFunction { min, max }
+    }
+}
@@ -0,0 +1,220 @@
+use irisia::{
+    build2,
+    hook::Signal,
+    model::{component::Component, control_flow::CommonVModel, prim::Block},
+    prim_element::block::BlockStyle,
+    primitive::Length,
+    style, Size,
+};
+
+pub use taffy::{AlignContent, GridAutoFlow, GridTrackRepetition, JustifyContent};
+
+use crate::layouts::{
+    base_style::ChildStyle, grid::implement::GridBlockLayout, AlignItems, AlignSelf,
+};
+
+mod implement;
+
+#[derive(Default)]
+pub struct Grid {
+    pub style: Option<Signal<GridContainerStyle>>,
+    pub children: Option<Signal<dyn CommonVModel<GridItemStyle>>>,
+}
+
+impl Component for Grid {
+    fn create(
+        self,
+        _watcher_list: &mut irisia::hook::watcher::WatcherList,
+    ) -> impl irisia::model::VNode<()> + use<> {
+        Signal::memo_ncmp(self.style, move |style| {
+            let style = style.cloned().unwrap_or_default();
+            build2! {
+                Block {
+                    display: GridBlockLayout {
+                        container_style: style.clone(),
+                    },
+                    style: style.base,
+
+                    (self.children.clone())
+                }
+            }
+        })
+    }
+}
+
+#[style(GridContainerStyleExt, interpolate)]
+#[derive(Clone, PartialEq)]
+pub struct GridContainerStyle {
+    #[style(discrete)]
+    pub grid_template_rows: Vec<GridTrack>,
+    #[style(discrete)]
+    pub grid_template_columns: Vec<GridTrack>,
+    /// Size of the rows created implicitly, cycled if more than one.
+    #[style(discrete)]
+    pub grid_auto_rows: Vec<TrackSize>,
+    /// Size of the columns created implicitly, cycled if more than one.
+    #[style(discrete)]
+    pub grid_auto_columns: Vec<TrackSize>,
+    #[style(discrete)]
+    pub grid_auto_flow: GridAutoFlow,
+    pub gap: Size<Length>,
+    #[style(discrete)]
+    pub align_content: AlignContent,
+    #[style(discrete)]
+    pub justify_content: JustifyContent,
+    #[style(discrete)]
+    pub align_items: AlignItems,
+    #[style(discrete)]
+    pub justify_items: AlignItems,
+
+    #[style(extend)]
+    pub base: BlockStyle,
+}
+
+impl GridContainerStyle {
+    pub const DEFAULT: Self = Self {
+        grid_template_rows: Vec::new(),
+        grid_template_columns: Vec::new(),
+        grid_auto_rows: Vec::new(),
+        grid_auto_columns: Vec::new(),
+        grid_auto_flow: GridAutoFlow::Row,
+        gap: Size::all(Length::Auto),
+        align_content: AlignContent::Stretch,
+        justify_content: JustifyContent::Stretch,
+        align_items: AlignItems::Auto,
+        justify_items: AlignItems::Auto,
+        base: BlockStyle::DEFAULT,
+    };
+}
+
+impl Default for GridContainerStyle {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+#[style(GridItemStyleExt)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct GridItemStyle {
+    pub grid_row_start: GridPlacement,
+    pub grid_row_end: GridPlacement,
+    pub grid_column_start: GridPlacement,
+    pub grid_column_end: GridPlacement,
+    pub align_self: AlignSelf,
+    pub justify_self: AlignSelf,
+
+    #[style(extend)]
+    pub base: ChildStyle,
+}
+
+impl GridItemStyle {
+    pub const DEFAULT: Self = Self {
+        grid_row_start: GridPlacement::Auto,
+        grid_row_end: GridPlacement::Auto,
+        grid_column_start: GridPlacement::Auto,
+        grid_column_end: GridPlacement::Auto,
+        align_self: AlignSelf::Auto,
+        justify_self: AlignSelf::Auto,
+        base: ChildStyle::DEFAULT,
+    };
+}
+
+impl Default for GridItemStyle {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// One entry of `grid_template_rows` or `grid_template_columns`.
+#[derive(Clone, PartialEq, Debug)]
+pub enum GridTrack {
+    Single(TrackSize),
+    /// Like `repeat()` in css. Auto repetitions only work if all the tracks
+    /// of the template have a fixed size.
+    Repeat(GridTrackRepetition, Vec<TrackSize>),
+}
+
+impl GridTrack {
+    pub fn repeat(count: u16, tracks: impl Into<Vec<TrackSize>>) -> Self {
+        Self::Repeat(GridTrackRepetition::Count(count), tracks.into())
+    }
+}
+
+impl From<TrackSize> for GridTrack {
+    fn from(value: TrackSize) -> Self {
+        Self::Single(value)
+    }
+}
+
+impl From<Length> for GridTrack {
+    fn from(value: Length) -> Self {
+        Self::Single(value.into())
+    }
+}
+
+/// Size of a grid track, the track sizes between `min` and `max` depending
+/// on its content and the free space.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct TrackSize {
+    pub min: MinTrackSize,
+    pub max: MaxTrackSize,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MinTrackSize {
+    Auto,
+    /// `Length::Auto` is the same as `MinTrackSize::Auto`.
+    Length(Length),
+    MinContent,
+    MaxContent,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MaxTrackSize {
+    Auto,
+    /// `Length::Auto` is the same as `MaxTrackSize::Auto`.
+    Length(Length),
+    MinContent,
+    MaxContent,
+    FitContent(Length),
+    /// Share of the free space, like the `fr` unit in css.
+    Fr(f32),
+}
+
+impl TrackSize {
+    pub const AUTO: Self = Self::minmax(MinTrackSize::Auto, MaxTrackSize::Auto);
+    pub const MIN_CONTENT: Self = Self::minmax(MinTrackSize::MinContent, MaxTrackSize::MinContent);
+    pub const MAX_CONTENT: Self = Self::minmax(MinTrackSize::MaxContent, MaxTrackSize::MaxContent);
+
+    pub const fn minmax(min: MinTrackSize, max: MaxTrackSize) -> Self {
+        Self { min, max }
+    }
+
+    pub const fn length(length: Length) -> Self {
+        Self::minmax(MinTrackSize::Length(length), MaxTrackSize::Length(length))
+    }
+
+    /// `TrackSize::fr(1.0)` is `1fr` in css, which means `minmax(auto, 1fr)`.
+    pub const fn fr(fr: f32) -> Self {
+        Self::minmax(MinTrackSize::Auto, MaxTrackSize::Fr(fr))
+    }
+
+    pub const fn fit_content(limit: Length) -> Self {
+        Self::minmax(MinTrackSize::Auto, MaxTrackSize::FitContent(limit))
+    }
+}
+
+impl From<Length> for TrackSize {
+    fn from(value: Length) -> Self {
+        Self::length(value)
+    }
+}
+
+/// Start or end of a grid item in one axis. Lines are counted from 1, and
+/// negative lines are counted backwards from the end of the explicit grid.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GridPlacement {
+    Auto,
+    Line(i16),
+    Span(u16),
+}
@@ -3,13 +3,20 @@ use std::{ops::Range, u64};
 use irisia::{
     prim_element::{block::layout::LayoutChildren, layout::SpaceConstraint},
     primitive::{length::LengthStandard, Size},
+    Point,
 };
 use taffy::{CoreStyle, LayoutPartialTree, NodeId, TraversePartialTree};
 
+pub use column::*;
 pub use flexbox::*;
+pub use grid::*;
+pub use stack::*;
 
 pub mod base_style;
+pub mod column;
 pub mod flexbox;
+pub mod grid;
+pub mod stack;
 
 struct MyTree<'a, Cts, Cds> {
     container_style: &'a Cts,
@@ -64,10 +71,13 @@ where
     ) -> taffy::LayoutOutput {
         let child_index = usize::from(node_id);
         let (constraint, pct_ref) = taffy_layout_input_to_local(&inputs);
-        let measured_size = self
-            .children
-            .get(child_index)
-            .compute_layout(constraint, pct_ref);
+        let child = self.children.get(child_index);
+        let measured_size = match inputs.run_mode {
+            taffy::RunMode::PerformLayout => child.compute_layout(constraint, pct_ref),
+            taffy::RunMode::ComputeSize | taffy::RunMode::PerformHiddenLayout => {
+                child.measure(constraint, pct_ref)
+            }
+        };
 
         taffy::LayoutOutput {
             size: taffy::Size {
@@ -141,6 +151,22 @@ fn local_layout_input_to_taffy(
     }
 }
 
+/// Mirror the children of a container `width` wide under the rtl direction,
+/// taffy has no notion of inline direction and lays them out from the left.
+fn mirror_children<Cd>(children: &LayoutChildren<Cd>, width: f32) {
+    if !children.direction().is_rtl() {
+        return;
+    }
+
+    for child in children.iter() {
+        let layout = child.layout();
+        child.set_location(Point {
+            x: width - layout.location.x - layout.size.width,
+            y: layout.location.y,
+        });
+    }
+}
+
 fn assert_parent_node(node_id: NodeId) {
     assert_eq!(node_id, PARENT_NODE_ID, "parent node id must be `u64::MAX`");
 }
@@ -0,0 +1,180 @@
+use irisia::{
+    prim_element::{
+        block::{layout::LayoutChildren, BlockLayout},
+        layout::SpaceConstraint,
+    },
+    Point, Size,
+};
+use taffy::{
+    style_helpers::line, AlignContent, AlignSelf, JustifyContent, LayoutGridContainer,
+    LayoutPartialTree, NonRepeatedTrackSizingFunction, TrackSizingFunction,
+};
+
+use crate::layouts::{
+    local_layout_input_to_taffy, mirror_children, size_to_local, CoreStyleMigration, MyTree,
+    ResolvedStyle, PARENT_NODE_ID,
+};
+
+use super::{StackAnchor, StackItemStyle};
+
+/// A grid of a single cell which all the children are put in.
+#[derive(Clone, Copy)]
+pub(super) struct StackBlockLayout;
+
+impl BlockLayout<StackItemStyle> for StackBlockLayout {
+    fn compute_layout(
+        &self,
+        children: LayoutChildren<StackItemStyle>,
+        constraints: Size<SpaceConstraint>,
+    ) -> Size<f32> {
+        let parent_size = children.parent_size().map(Some);
+        let mut tree = MyTree {
+            container_style: self,
+            children,
+        };
+
+        let size = size_to_local(
+            taffy::compute_grid_layout(
+                &mut tree,
+                PARENT_NODE_ID,
+                local_layout_input_to_taffy(constraints, parent_size),
+            )
+            .size,
+        );
+
+        // taffy knows no offsets, so they are applied by hand
+        let children = tree.children;
+        let length_standard = children.length_standard().as_ref().to_point();
+        for child in children.iter() {
+            let layout = child.layout();
+            let offset = child
+                .data()
+                .offset
+                .map_with(length_standard, |len, ls| ls.resolve(len).unwrap_or(0.0));
+
+            child.set_location(Point {
+                x: layout.location.x + offset.x,
+                y: layout.location.y + offset.y,
+            });
+        }
+
+        mirror_children(&children, size.width);
+        size
+    }
+}
+
+impl<'tree> LayoutGridContainer for MyTree<'tree, StackBlockLayout, StackItemStyle> {
+    type GridContainerStyle<'a>
+        = ResolvedStyle<'a, &'a StackBlockLayout>
+    where
+        Self: 'a;
+
+    type GridItemStyle<'a>
+        = ResolvedStyle<'a, &'a StackItemStyle>
+    where
+        Self: 'a;
+
+    fn get_grid_container_style(&self, node_id: taffy::NodeId) -> Self::GridContainerStyle<'_> {
+        self.get_core_container_style(node_id)
+    }
+
+    fn get_grid_child_style(&self, child_node_id: taffy::NodeId) -> Self::GridItemStyle<'_> {
+        ResolvedStyle {
+            length_standard: self.children.length_standard().as_ref(),
+            style: self.children.get(child_node_id.into()).data(),
+        }
+    }
+}
+
+impl taffy::CoreStyle for ResolvedStyle<'_, &StackBlockLayout> {
+    // everything use default
+}
+
+impl taffy::GridContainerStyle for ResolvedStyle<'_, &StackBlockLayout> {
+    type TemplateTrackList<'a>
+        = &'a [TrackSizingFunction]
+    where
+        Self: 'a;
+
+    type AutoTrackList<'a>
+        = &'a [NonRepeatedTrackSizingFunction]
+    where
+        Self: 'a;
+
+    // the only cell is an implicit auto track in both axes
+    fn grid_template_rows(&self) -> Self::TemplateTrackList<'_> {
+        &[]
+    }
+
+    fn grid_template_columns(&self) -> Self::TemplateTrackList<'_> {
+        &[]
+    }
+
+    fn grid_auto_rows(&self) -> Self::AutoTrackList<'_> {
+        &[]
+    }
+
+    fn grid_auto_columns(&self) -> Self::AutoTrackList<'_> {
+        &[]
+    }
+
+    fn align_content(&self) -> Option<AlignContent> {
+        Some(AlignContent::Stretch)
+    }
+
+    fn justify_content(&self) -> Option<JustifyContent> {
+        Some(JustifyContent::Stretch)
+    }
+}
+
+impl<'a, 'b> CoreStyleMigration for ResolvedStyle<'a, &'b StackItemStyle> {
+    fn as_core_style(&self) -> impl taffy::CoreStyle + use<'_, 'a, 'b> {
+        self.wrap(&self.style.base)
+    }
+}
+
+impl taffy::GridItemStyle for ResolvedStyle<'_, &StackItemStyle> {
+    fn grid_row(&self) -> taffy::Line<taffy::GridPlacement> {
+        taffy::Line {
+            start: line(1),
+            end: line(2),
+        }
+    }
+
+    fn grid_column(&self) -> taffy::Line<taffy::GridPlacement> {
+        taffy::Line {
+            start: line(1),
+            end: line(2),
+        }
+    }
+
+    fn align_self(&self) -> Option<AlignSelf> {
+        Some(self.style.anchor.to_taffy(self.style.stretch).y)
+    }
+
+    fn justify_self(&self) -> Option<AlignSelf> {
+        Some(self.style.anchor.to_taffy(self.style.stretch).x)
+    }
+}
+
+impl StackAnchor {
+    fn to_taffy(self, stretch: Point<bool>) -> Point<AlignSelf> {
+        use StackAnchor::*;
+
+        let x = match self {
+            TopStart | Start | BottomStart => AlignSelf::Start,
+            Top | Center | Bottom => AlignSelf::Center,
+            TopEnd | End | BottomEnd => AlignSelf::End,
+        };
+        let y = match self {
+            TopStart | Top | TopEnd => AlignSelf::Start,
+            Start | Center | End => AlignSelf::Center,
+            BottomStart | Bottom | BottomEnd => AlignSelf::End,
+        };
+
+        Point {
+            x: if stretch.x { AlignSelf::Stretch } else { x },
+            y: if stretch.y { AlignSelf::Stretch } else { y },
+        }
+    }
+}
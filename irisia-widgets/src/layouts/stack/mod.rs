@@ -0,0 +1,85 @@
+use irisia::{
+    build2,
+    hook::Signal,
+    model::{component::Component, control_flow::CommonVModel, prim::Block},
+    prim_element::block::BlockStyle,
+    primitive::Length,
+    style, Point,
+};
+
+use crate::layouts::{base_style::ChildStyle, stack::implement::StackBlockLayout};
+
+mod implement;
+
+/// Layers children in the same box, the later ones above. Each child is put
+/// on one of the 9 anchor points of the box and then moved by its offset, the
+/// box is as large as the largest child unless it has a size.
+#[derive(Default)]
+pub struct Stack {
+    pub style: Option<Signal<BlockStyle>>,
+    pub children: Option<Signal<dyn CommonVModel<StackItemStyle>>>,
+}
+
+impl Component for Stack {
+    fn create(
+        self,
+        _watcher_list: &mut irisia::hook::watcher::WatcherList,
+    ) -> impl irisia::model::VNode<()> + use<> {
+        Signal::memo_ncmp(self.style, move |style| {
+            let style = style.cloned().unwrap_or_default();
+            build2! {
+                Block {
+                    display: StackBlockLayout,
+                    style: style,
+
+                    (self.children.clone())
+                }
+            }
+        })
+    }
+}
+
+#[style(StackItemStyleExt)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct StackItemStyle {
+    pub anchor: StackAnchor,
+    /// Moves the child from its anchor without affecting the size of the
+    /// stack, positive `x` is towards the inline end.
+    pub offset: Point<Length>,
+    /// Fill the stack in the axis if the child has no size in it, the anchor
+    /// is ignored in the axis then.
+    pub stretch: Point<bool>,
+
+    #[style(extend)]
+    pub base: ChildStyle,
+}
+
+impl StackItemStyle {
+    pub const DEFAULT: Self = Self {
+        anchor: StackAnchor::TopStart,
+        offset: Point::all(Length::ZERO),
+        stretch: Point::all(false),
+        base: ChildStyle::DEFAULT,
+    };
+}
+
+impl Default for StackItemStyle {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// The point of the stack a child is aligned to, the same point of the child
+/// is put on it. Start and end follow the inline direction.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StackAnchor {
+    TopStart,
+    Top,
+    TopEnd,
+    Start,
+    Center,
+    End,
+    BottomStart,
+    Bottom,
+    BottomEnd,
+}
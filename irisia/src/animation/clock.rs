@@ -0,0 +1,108 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::{Rc, Weak},
+    sync::Arc,
+    time::Duration,
+};
+
+use irisia_backend::WinitWindow;
+
+thread_local! {
+    static CURRENT: RefCell<Weak<Inner>> = const { RefCell::new(Weak::new()) };
+}
+
+/// Drives the animations of a window, ticked once per frame.
+///
+/// The clock keeps requesting redraws only while there are running
+/// animations, so an idle window does not wake up.
+#[derive(Clone)]
+pub struct FrameClock {
+    inner: Rc<Inner>,
+}
+
+struct Inner {
+    window: Arc<WinitWindow>,
+    tickers: RefCell<Vec<Ticker>>,
+    running: Cell<bool>,
+}
+
+struct Ticker {
+    started: bool,
+    tick: Box<dyn FnMut(Duration) -> bool>,
+}
+
+impl FrameClock {
+    pub(crate) fn new(window: Arc<WinitWindow>) -> Self {
+        let this = Self {
+            inner: Rc::new(Inner {
+                window,
+                tickers: RefCell::new(Vec::new()),
+                running: Cell::new(false),
+            }),
+        };
+
+        // every window runs on its own thread
+        CURRENT.with_borrow_mut(|current| *current = Rc::downgrade(&this.inner));
+        this
+    }
+
+    /// Returns the clock of the window running on the current thread.
+    ///
+    /// # Panics
+    /// Panics if called outside of a window, use [`FrameClock::try_current`]
+    /// to check it.
+    pub fn current() -> Self {
+        Self::try_current().expect("frame clock can only be used inside a window")
+    }
+
+    pub fn try_current() -> Option<Self> {
+        CURRENT
+            .with_borrow(Weak::upgrade)
+            .map(|inner| Self { inner })
+    }
+
+    /// Whether there are animations waiting for the next frame.
+    pub fn is_running(&self) -> bool {
+        self.inner.running.get()
+    }
+
+    /// Calls `tick` every frame with the time elapsed since the previous one,
+    /// until it returns `false`. The first call is made on the next frame with
+    /// zero elapsed.
+    pub fn add_ticker<F>(&self, tick: F)
+    where
+        F: FnMut(Duration) -> bool + 'static,
+    {
+        self.inner.tickers.borrow_mut().push(Ticker {
+            started: false,
+            tick: Box::new(tick),
+        });
+
+        if !self.inner.running.replace(true) {
+            self.inner.window.request_redraw();
+        }
+    }
+
+    /// Returns whether another frame is needed.
+    pub(crate) fn tick(&self, interval: Duration) -> bool {
+        // tickers may start other animations while running
+        let mut tickers = self.inner.tickers.take();
+        tickers.retain_mut(|ticker| {
+            let elapsed = if ticker.started {
+                interval
+            } else {
+                ticker.started = true;
+                Duration::ZERO
+            };
+            (ticker.tick)(elapsed)
+        });
+
+        let mut list = self.inner.tickers.borrow_mut();
+        tickers.append(&mut list);
+        *list = tickers;
+
+        let running = !list.is_empty();
+        self.inner.running.set(running);
+        running
+    }
+}
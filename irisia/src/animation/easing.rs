@@ -0,0 +1,117 @@
+/// Maps the progress of an animation in time to the progress of its value,
+/// both from 0 to 1.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum Easing {
+    Linear,
+    /// Same as `ease` in css.
+    #[default]
+    Ease,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    /// Control points of a cubic bezier curve from `(0, 0)` to `(1, 1)`, like
+    /// `cubic-bezier(x1, y1, x2, y2)` in css.
+    CubicBezier(f32, f32, f32, f32),
+    Custom(fn(f32) -> f32),
+}
+
+impl Easing {
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match *self {
+            Self::Linear => t,
+            Self::Ease => cubic_bezier(0.25, 0.1, 0.25, 1.0, t),
+            Self::EaseIn => cubic_bezier(0.42, 0.0, 1.0, 1.0, t),
+            Self::EaseOut => cubic_bezier(0.0, 0.0, 0.58, 1.0, t),
+            Self::EaseInOut => cubic_bezier(0.42, 0.0, 0.58, 1.0, t),
+            Self::CubicBezier(x1, y1, x2, y2) => cubic_bezier(x1, y1, x2, y2, t),
+            Self::Custom(f) => f(t),
+        }
+    }
+}
+
+impl PartialEq for Easing {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::CubicBezier(a1, b1, c1, d1), Self::CubicBezier(a2, b2, c2, d2)) => {
+                (a1, b1, c1, d1) == (a2, b2, c2, d2)
+            }
+            (Self::Custom(a), Self::Custom(b)) => std::ptr::fn_addr_eq(*a, *b),
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, x: f32) -> f32 {
+    // one dimension of the curve, `p0` is 0 and `p3` is 1
+    let curve = |p1: f32, p2: f32, s: f32| {
+        let r = 1.0 - s;
+        3.0 * r * r * s * p1 + 3.0 * r * s * s * p2 + s * s * s
+    };
+    let slope = |p1: f32, p2: f32, s: f32| {
+        let r = 1.0 - s;
+        3.0 * r * r * p1 + 6.0 * r * s * (p2 - p1) + 3.0 * s * s * (1.0 - p2)
+    };
+
+    if x <= 0.0 || x >= 1.0 {
+        return x;
+    }
+
+    // find the curve parameter of `x`, newton's method converges fast in
+    // most cases, and bisection catches the flat ones.
+    let mut s = x;
+    for _ in 0..8 {
+        let error = curve(x1, x2, s) - x;
+        if error.abs() < 1e-6 && (0.0..=1.0).contains(&s) {
+            return curve(y1, y2, s);
+        }
+        let d = slope(x1, x2, s);
+        if d.abs() < 1e-6 {
+            break;
+        }
+        s -= error / d;
+    }
+
+    let (mut low, mut high) = (0.0, 1.0);
+    s = x;
+    for _ in 0..32 {
+        let value = curve(x1, x2, s);
+        if (value - x).abs() < 1e-6 {
+            break;
+        }
+        if value < x {
+            low = s;
+        } else {
+            high = s;
+        }
+        s = (low + high) / 2.0;
+    }
+
+    curve(y1, y2, s)
+}
+
+#[test]
+fn test() {
+    for easing in [
+        Easing::Linear,
+        Easing::Ease,
+        Easing::EaseIn,
+        Easing::EaseOut,
+        Easing::EaseInOut,
+    ] {
+        assert_eq!(easing.apply(0.0), 0.0);
+        assert_eq!(easing.apply(1.0), 1.0);
+
+        let mut prev = 0.0;
+        for i in 1..=100 {
+            let value = easing.apply(i as f32 / 100.0);
+            assert!(value >= prev, "{easing:?} is not monotonic");
+            prev = value;
+        }
+    }
+
+    assert!((Easing::Linear.apply(0.3) - 0.3).abs() < 1e-6);
+    assert!((Easing::EaseInOut.apply(0.5) - 0.5).abs() < 1e-4);
+    assert!(Easing::EaseIn.apply(0.5) < 0.5);
+    assert!(Easing::EaseOut.apply(0.5) > 0.5);
+}
@@ -0,0 +1,63 @@
+use irisia_backend::skia_safe::{Color, Color4f};
+
+/// Values which can be animated.
+///
+/// Can be derived for style structs with `#[style(TraitName, interpolate)]`,
+/// fields marked with `#[style(discrete)]` are switched with [`discrete`].
+pub trait Interpolate: Clone {
+    /// Returns the value at `t` of the way from `self` to `to`. `t` is usually
+    /// from 0 to 1, but may overshoot with some easings.
+    fn interpolate(&self, to: &Self, t: f32) -> Self;
+}
+
+/// Interpolation of values that can't change gradually, switching from `from`
+/// to `to` at the halfway.
+pub fn discrete<T: Clone>(from: &T, to: &T, t: f32) -> T {
+    if t < 0.5 { from.clone() } else { to.clone() }
+}
+
+impl Interpolate for f32 {
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Interpolate for f64 {
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        self + (to - self) * t as f64
+    }
+}
+
+/// Interpolated with premultiplied alpha, so fading from or to a transparent
+/// color doesn't go through black.
+impl Interpolate for Color {
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        let premul = |color: &Color| {
+            let Color4f { r, g, b, a } = Color4f::from(*color);
+            [r * a, g * a, b * a, a]
+        };
+
+        let (from, to) = (premul(self), premul(to));
+        let [r, g, b, a] = [0, 1, 2, 3].map(|i| from[i].interpolate(&to[i], t).clamp(0.0, 1.0));
+        if a == 0.0 {
+            return Color::TRANSPARENT;
+        }
+
+        Color4f::new(r / a, g / a, b / a, a).to_color()
+    }
+}
+
+/// Pairs are interpolated if both have the same length, otherwise switched
+/// with [`discrete`].
+impl<T: Interpolate> Interpolate for Vec<T> {
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        if self.len() != to.len() {
+            return discrete(self, to, t);
+        }
+
+        self.iter()
+            .zip(to)
+            .map(|(from, to)| from.interpolate(to, t))
+            .collect()
+    }
+}
@@ -0,0 +1,225 @@
+use std::time::Duration;
+
+use crate::hook::signal::WriteSignal;
+
+use super::{Animation, Easing, FrameClock, Interpolate};
+
+/// A timeline going through several values, like `@keyframes` in css.
+#[derive(Clone, Debug)]
+pub struct Keyframes<T> {
+    frames: Vec<Keyframe<T>>,
+    duration: Duration,
+    repeat: Repeat,
+    direction: PlaybackDirection,
+}
+
+#[derive(Clone, Debug)]
+struct Keyframe<T> {
+    offset: f32,
+    value: T,
+    /// Easing to the next keyframe.
+    easing: Easing,
+}
+
+/// How many times the timeline plays.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Repeat {
+    Times(u32),
+    Forever,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum PlaybackDirection {
+    #[default]
+    Normal,
+    Reverse,
+    /// Forwards on the first play, backwards on the second, and so on.
+    Alternate,
+    AlternateReverse,
+}
+
+impl<T: Interpolate> Keyframes<T> {
+    /// `duration` is the time of one play.
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            frames: Vec::new(),
+            duration,
+            repeat: Repeat::Times(1),
+            direction: PlaybackDirection::Normal,
+        }
+    }
+
+    /// Adds a keyframe at `offset`, from 0 to 1 of a play. The value eases to
+    /// the next keyframe linearly.
+    pub fn frame(self, offset: f32, value: T) -> Self {
+        self.frame_eased(offset, value, Easing::Linear)
+    }
+
+    pub fn frame_eased(mut self, offset: f32, value: T, easing: Easing) -> Self {
+        let offset = offset.clamp(0.0, 1.0);
+        // keyframes at the same offset keep their order
+        let index = self.frames.partition_point(|frame| frame.offset <= offset);
+        self.frames.insert(
+            index,
+            Keyframe {
+                offset,
+                value,
+                easing,
+            },
+        );
+        self
+    }
+
+    pub fn repeat(mut self, repeat: Repeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    pub fn direction(mut self, direction: PlaybackDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Total time of all the plays, `None` if it repeats forever.
+    pub fn total_duration(&self) -> Option<Duration> {
+        match self.repeat {
+            Repeat::Times(times) => self.duration.checked_mul(times),
+            Repeat::Forever => None,
+        }
+    }
+
+    fn plays(&self) -> u32 {
+        match self.repeat {
+            Repeat::Times(times) => times,
+            Repeat::Forever => u32::MAX,
+        }
+    }
+
+    /// Returns the value at `elapsed` and whether the timeline has ended.
+    /// Returns `None` if there are no keyframes.
+    pub fn sample(&self, elapsed: Duration) -> Option<(T, bool)> {
+        if self.frames.is_empty() {
+            return None;
+        }
+
+        let duration = self.duration.as_secs_f64();
+        let (play, progress, ended) = match self.total_duration() {
+            // stay at the end of the last play
+            Some(total) if elapsed >= total => (self.plays().saturating_sub(1), 1.0, true),
+            _ if duration == 0.0 => (0, 1.0, false),
+            _ => {
+                let plays = elapsed.as_secs_f64() / duration;
+                (plays as u32, plays.fract() as f32, false)
+            }
+        };
+
+        let forwards = match self.direction {
+            PlaybackDirection::Normal => true,
+            PlaybackDirection::Reverse => false,
+            PlaybackDirection::Alternate => play % 2 == 0,
+            PlaybackDirection::AlternateReverse => play % 2 == 1,
+        };
+        let progress = if forwards { progress } else { 1.0 - progress };
+
+        Some((self.value_at(progress), ended))
+    }
+
+    fn value_at(&self, progress: f32) -> T {
+        let next = self
+            .frames
+            .partition_point(|frame| frame.offset <= progress);
+
+        let (from, to) = match (next.checked_sub(1), self.frames.get(next)) {
+            (Some(prev), Some(next)) => (&self.frames[prev], next),
+            (Some(prev), None) => return self.frames[prev].value.clone(),
+            (None, _) => return self.frames[0].value.clone(),
+        };
+
+        let t = (progress - from.offset) / (to.offset - from.offset);
+        from.value.interpolate(&to.value, from.easing.apply(t))
+    }
+
+    /// Plays the timeline into `signal` on the frame clock of the current
+    /// window, the first value is written immediately.
+    ///
+    /// # Panics
+    /// Panics if called outside of a window.
+    pub fn play(&self, signal: &WriteSignal<T>) -> Animation
+    where
+        T: 'static,
+    {
+        self.play_on(&FrameClock::current(), signal)
+    }
+
+    /// Same as [`Keyframes::play`], but on the given clock.
+    pub fn play_on(&self, clock: &FrameClock, signal: &WriteSignal<T>) -> Animation
+    where
+        T: 'static,
+    {
+        let animation = Animation::new();
+        let Some((first, _)) = self.sample(Duration::ZERO) else {
+            animation.finish();
+            return animation;
+        };
+        signal.set(first);
+
+        let keyframes = self.clone();
+        let signal = signal.clone();
+        let handle = animation.clone();
+        let mut elapsed = Duration::ZERO;
+        clock.add_ticker(move |interval| {
+            if !handle.is_running() {
+                return false;
+            }
+
+            elapsed = elapsed.saturating_add(interval);
+            let (value, ended) = keyframes.sample(elapsed).unwrap();
+            signal.set(value);
+            if ended {
+                handle.finish();
+            }
+            !ended
+        });
+
+        animation
+    }
+}
+
+#[test]
+fn test_keyframes_sample() {
+    let keyframes = Keyframes::new(Duration::from_secs(1))
+        .frame(1.0, 20.0f32)
+        .frame(0.0, 0.0)
+        .frame(0.5, 10.0)
+        .repeat(Repeat::Times(2))
+        .direction(PlaybackDirection::Alternate);
+
+    let at = |ms| keyframes.sample(Duration::from_millis(ms)).unwrap();
+    let close = |(value, ended): (f32, bool), expected: f32, expected_ended: bool| {
+        assert!((value - expected).abs() < 1e-3, "{value} != {expected}");
+        assert_eq!(ended, expected_ended);
+    };
+
+    close(at(0), 0.0, false);
+    close(at(250), 5.0, false);
+    close(at(750), 15.0, false);
+    // backwards on the second play
+    close(at(1250), 15.0, false);
+    close(at(1999), 0.02, false);
+    close(at(2000), 0.0, true);
+    close(at(5000), 0.0, true);
+
+    let forever = keyframes.clone().repeat(Repeat::Forever);
+    close(
+        forever.sample(Duration::from_millis(4250)).unwrap(),
+        5.0,
+        false,
+    );
+    assert_eq!(forever.total_duration(), None);
+
+    assert!(
+        Keyframes::<f32>::new(Duration::ZERO)
+            .sample(Duration::ZERO)
+            .is_none()
+    );
+}
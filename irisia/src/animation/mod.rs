@@ -0,0 +1,17 @@
+pub use self::{
+    clock::FrameClock,
+    easing::Easing,
+    interpolate::{Interpolate, discrete},
+    keyframes::{Keyframes, PlaybackDirection, Repeat},
+    spring::{Spring, SpringAnimation, SpringValue},
+    transition::Transition,
+    tween::{Animation, AnimationState, animate, animate_on},
+};
+
+mod clock;
+mod easing;
+mod interpolate;
+mod keyframes;
+mod spring;
+mod transition;
+mod tween;
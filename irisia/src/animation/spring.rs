@@ -0,0 +1,368 @@
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+    time::Duration,
+};
+
+use crate::{
+    hook::signal::WriteSignal,
+    primitive::{Point, Size},
+};
+
+use super::FrameClock;
+
+// larger steps make stiff springs explode
+const MAX_SUBSTEP: f32 = 1.0 / 240.0;
+// a long stall is treated as a short one instead of simulating it all
+const MAX_FRAME_TIME: Duration = Duration::from_millis(100);
+
+/// Parameters of a damped spring.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Spring {
+    pub stiffness: f32,
+    pub damping: f32,
+    pub mass: f32,
+    /// The spring rests once both the distance to the target and the speed
+    /// per second are below it, in the unit of the value.
+    pub precision: f32,
+}
+
+impl Spring {
+    pub const DEFAULT: Self = Self {
+        stiffness: 170.0,
+        damping: 26.0,
+        mass: 1.0,
+        precision: 0.01,
+    };
+
+    /// Slower, with a little bounce.
+    pub const GENTLE: Self = Self {
+        stiffness: 120.0,
+        damping: 14.0,
+        ..Self::DEFAULT
+    };
+
+    /// Bounces around the target before resting.
+    pub const WOBBLY: Self = Self {
+        stiffness: 180.0,
+        damping: 12.0,
+        ..Self::DEFAULT
+    };
+
+    pub const fn new(stiffness: f32, damping: f32) -> Self {
+        Self {
+            stiffness,
+            damping,
+            ..Self::DEFAULT
+        }
+    }
+
+    pub const fn mass(mut self, mass: f32) -> Self {
+        self.mass = mass;
+        self
+    }
+
+    pub const fn precision(mut self, precision: f32) -> Self {
+        self.precision = precision;
+        self
+    }
+}
+
+impl Default for Spring {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Values which can be moved by a spring, seen as vectors.
+pub trait SpringValue: Clone + 'static {
+    /// Returns `self + delta * scale`.
+    fn add_scaled(&self, delta: &Self, scale: f32) -> Self;
+
+    /// Returns `self - other`.
+    fn difference(&self, other: &Self) -> Self;
+
+    /// Returns the length as a vector.
+    fn magnitude(&self) -> f32;
+}
+
+impl SpringValue for f32 {
+    fn add_scaled(&self, delta: &Self, scale: f32) -> Self {
+        self + delta * scale
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        self - other
+    }
+
+    fn magnitude(&self) -> f32 {
+        self.abs()
+    }
+}
+
+impl SpringValue for f64 {
+    fn add_scaled(&self, delta: &Self, scale: f32) -> Self {
+        self + delta * scale as f64
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        self - other
+    }
+
+    fn magnitude(&self) -> f32 {
+        self.abs() as f32
+    }
+}
+
+impl<T: SpringValue> SpringValue for Point<T> {
+    fn add_scaled(&self, delta: &Self, scale: f32) -> Self {
+        Point {
+            x: self.x.add_scaled(&delta.x, scale),
+            y: self.y.add_scaled(&delta.y, scale),
+        }
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        Point {
+            x: self.x.difference(&other.x),
+            y: self.y.difference(&other.y),
+        }
+    }
+
+    fn magnitude(&self) -> f32 {
+        self.x.magnitude().hypot(self.y.magnitude())
+    }
+}
+
+impl<T: SpringValue> SpringValue for Size<T> {
+    fn add_scaled(&self, delta: &Self, scale: f32) -> Self {
+        Size {
+            width: self.width.add_scaled(&delta.width, scale),
+            height: self.height.add_scaled(&delta.height, scale),
+        }
+    }
+
+    fn difference(&self, other: &Self) -> Self {
+        Size {
+            width: self.width.difference(&other.width),
+            height: self.height.difference(&other.height),
+        }
+    }
+
+    fn magnitude(&self) -> f32 {
+        self.width.magnitude().hypot(self.height.magnitude())
+    }
+}
+
+/// Moves a signal towards a target like a spring. Retargeting keeps the
+/// current velocity, so the motion can be interrupted at any time.
+///
+/// The spring stops when all the clones of it are dropped.
+pub struct SpringAnimation<T> {
+    state: Rc<RefCell<SpringState<T>>>,
+}
+
+struct SpringState<T> {
+    clock: FrameClock,
+    signal: WriteSignal<T>,
+    motion: SpringMotion<T>,
+    ticking: bool,
+}
+
+/// The simulated part of a spring, apart from the clock and the signal.
+struct SpringMotion<T> {
+    spring: Spring,
+    value: T,
+    target: T,
+    velocity: T,
+}
+
+impl<T: SpringValue> SpringAnimation<T> {
+    /// Creates a spring resting at the current value of `signal`, on the
+    /// frame clock of the current window.
+    ///
+    /// # Panics
+    /// Panics if called outside of a window.
+    pub fn new(signal: &WriteSignal<T>, spring: Spring) -> Self {
+        Self::new_on(&FrameClock::current(), signal, spring)
+    }
+
+    /// Same as [`SpringAnimation::new`], but on the given clock.
+    pub fn new_on(clock: &FrameClock, signal: &WriteSignal<T>, spring: Spring) -> Self {
+        Self {
+            state: Rc::new(RefCell::new(SpringState {
+                clock: clock.clone(),
+                signal: signal.clone(),
+                motion: SpringMotion::resting(spring, signal.read().clone()),
+                ticking: false,
+            })),
+        }
+    }
+
+    /// Moves towards `target` from where it is, keeping the velocity.
+    pub fn set_target(&self, target: T) {
+        self.state.borrow_mut().motion.target = target;
+        self.wake();
+    }
+
+    /// Sets the velocity per second, like flinging it at the end of a drag.
+    pub fn set_velocity(&self, velocity: T) {
+        self.state.borrow_mut().motion.velocity = velocity;
+        self.wake();
+    }
+
+    /// Places the spring at `value` and rests it there. The signal is written
+    /// immediately, this is what following the pointer while dragging needs.
+    pub fn jump_to(&self, value: T) {
+        let signal = {
+            let mut state = self.state.borrow_mut();
+            state.motion.jump_to(value.clone());
+            state.signal.clone()
+        };
+        signal.set(value);
+    }
+
+    pub fn set_spring(&self, spring: Spring) {
+        self.state.borrow_mut().motion.spring = spring;
+    }
+
+    pub fn target(&self) -> T {
+        self.state.borrow().motion.target.clone()
+    }
+
+    pub fn velocity(&self) -> T {
+        self.state.borrow().motion.velocity.clone()
+    }
+
+    /// Whether the spring hasn't come to rest yet.
+    pub fn is_running(&self) -> bool {
+        self.state.borrow().ticking
+    }
+
+    fn wake(&self) {
+        let mut state = self.state.borrow_mut();
+        if state.ticking {
+            return;
+        }
+        state.ticking = true;
+
+        let weak = Rc::downgrade(&self.state);
+        state
+            .clock
+            .add_ticker(move |interval| tick(&weak, interval));
+    }
+}
+
+fn tick<T: SpringValue>(weak: &Weak<RefCell<SpringState<T>>>, interval: Duration) -> bool {
+    let Some(state) = weak.upgrade() else {
+        return false;
+    };
+
+    let (signal, value, ticking) = {
+        let mut state = state.borrow_mut();
+        state.ticking = state
+            .motion
+            .step(interval.min(MAX_FRAME_TIME).as_secs_f32());
+        (
+            state.signal.clone(),
+            state.motion.value.clone(),
+            state.ticking,
+        )
+    };
+
+    // the state is not borrowed here, watchers of the signal may retarget it
+    signal.set(value);
+    ticking
+}
+
+impl<T: SpringValue> SpringMotion<T> {
+    fn resting(spring: Spring, value: T) -> Self {
+        Self {
+            spring,
+            velocity: value.difference(&value),
+            target: value.clone(),
+            value,
+        }
+    }
+
+    fn jump_to(&mut self, value: T) {
+        self.velocity = value.difference(&value);
+        self.target = value.clone();
+        self.value = value;
+    }
+
+    /// Advances `dt` seconds, returns whether it's still moving.
+    fn step(&mut self, mut dt: f32) -> bool {
+        let Spring {
+            stiffness,
+            damping,
+            mass,
+            precision,
+        } = self.spring;
+
+        // semi-implicit euler
+        while dt > 0.0 {
+            let h = dt.min(MAX_SUBSTEP);
+            let displacement = self.value.difference(&self.target);
+            self.velocity = self
+                .velocity
+                .add_scaled(&displacement, -stiffness / mass * h)
+                .add_scaled(&self.velocity, -damping / mass * h);
+            self.value = self.value.add_scaled(&self.velocity, h);
+            dt -= h;
+        }
+
+        let distance = self.value.difference(&self.target).magnitude();
+        if distance < precision && self.velocity.magnitude() < precision {
+            self.jump_to(self.target.clone());
+            return false;
+        }
+        true
+    }
+}
+
+impl<T> Clone for SpringAnimation<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+#[test]
+fn test_spring_motion() {
+    let frame = 1.0 / 60.0;
+    let mut motion = SpringMotion::resting(Spring::DEFAULT, 0.0f32);
+
+    // settles on the target, then stops
+    motion.target = 100.0;
+    let mut frames = 0;
+    while motion.step(frame) {
+        frames += 1;
+        assert!(frames < 600, "the spring never rests");
+    }
+    assert_eq!(motion.value, 100.0);
+    assert_eq!(motion.velocity, 0.0);
+
+    // retargeting halfway keeps the velocity towards the old target
+    motion.target = 200.0;
+    for _ in 0..5 {
+        motion.step(frame);
+    }
+    let velocity = motion.velocity;
+    assert!(velocity > 0.0);
+    motion.target = 0.0;
+    assert_eq!(motion.velocity, velocity);
+    let value = motion.value;
+    assert!(motion.step(frame));
+    assert!(motion.value > value, "the motion turned around in a frame");
+
+    // jumping rests at once
+    motion.jump_to(50.0);
+    assert_eq!(
+        (motion.value, motion.target, motion.velocity),
+        (50.0, 50.0, 0.0)
+    );
+    assert!(!motion.step(frame));
+    assert_eq!(motion.value, 50.0);
+}
@@ -0,0 +1,83 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use crate::hook::Signal;
+
+use super::{Animation, Easing, FrameClock, Interpolate, tween::tween};
+
+/// How a value changes to a new one, like `transition` in css.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Transition {
+    pub duration: Duration,
+    pub easing: Easing,
+}
+
+struct TransitionState<T> {
+    from: T,
+    to: T,
+    animation: Option<Animation>,
+}
+
+impl Transition {
+    pub const fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            easing: Easing::Ease,
+        }
+    }
+
+    pub const fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Returns a signal following `source`. Every time `source` changes, the
+    /// returned signal is animated from the value it's showing to the new one,
+    /// so an interrupted transition continues smoothly.
+    ///
+    /// # Panics
+    /// Panics if called outside of a window.
+    pub fn apply<T>(&self, source: Signal<T>) -> Signal<T>
+    where
+        T: Interpolate + 'static,
+    {
+        let clock = FrameClock::current();
+        let Self { duration, easing } = *self;
+
+        let initial = source.read().clone();
+        let state = Rc::new(RefCell::new(TransitionState {
+            from: initial.clone(),
+            to: initial.clone(),
+            animation: None,
+        }));
+        let progress = Signal::state(1.0f32);
+
+        Signal::builder(initial)
+            .dep(
+                {
+                    let state = state.clone();
+                    let progress = progress.clone();
+                    move |this, to: &T| {
+                        let mut state = state.borrow_mut();
+                        if let Some(animation) = state.animation.take() {
+                            animation.cancel();
+                        }
+
+                        // `this` is left unchanged until the next frame
+                        state.from = (*this).clone();
+                        state.to = to.clone();
+                        state.animation =
+                            Some(tween(&clock, progress.clone(), 0.0, 1.0, duration, easing));
+                    }
+                },
+                source,
+            )
+            .dep(
+                move |mut this, &progress: &f32| {
+                    let state = state.borrow();
+                    *this = state.from.interpolate(&state.to, progress);
+                },
+                progress.to_signal(),
+            )
+            .build()
+    }
+}
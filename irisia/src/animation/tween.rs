@@ -0,0 +1,114 @@
+use std::{cell::Cell, rc::Rc, time::Duration};
+
+use crate::hook::signal::WriteSignal;
+
+use super::{Easing, FrameClock, Interpolate};
+
+/// Handle of a running animation. Dropping it does not stop the animation.
+#[derive(Clone)]
+pub struct Animation {
+    state: Rc<Cell<AnimationState>>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AnimationState {
+    Running,
+    Finished,
+    Cancelled,
+}
+
+impl Animation {
+    pub(crate) fn new() -> Self {
+        Self {
+            state: Rc::new(Cell::new(AnimationState::Running)),
+        }
+    }
+
+    /// Stops the animation, leaving the signal at its current value.
+    pub fn cancel(&self) {
+        if self.is_running() {
+            self.state.set(AnimationState::Cancelled);
+        }
+    }
+
+    pub fn state(&self) -> AnimationState {
+        self.state.get()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.state() == AnimationState::Running
+    }
+
+    pub(crate) fn finish(&self) {
+        self.state.set(AnimationState::Finished);
+    }
+}
+
+/// Animates `signal` from `from` to `to` on the frame clock of the current
+/// window. `from` is written immediately, and `to` is exactly written at last.
+///
+/// # Panics
+/// Panics if called outside of a window.
+pub fn animate<T>(
+    signal: &WriteSignal<T>,
+    from: T,
+    to: T,
+    duration: Duration,
+    easing: Easing,
+) -> Animation
+where
+    T: Interpolate + 'static,
+{
+    animate_on(&FrameClock::current(), signal, from, to, duration, easing)
+}
+
+/// Same as [`animate`], but on the given clock.
+pub fn animate_on<T>(
+    clock: &FrameClock,
+    signal: &WriteSignal<T>,
+    from: T,
+    to: T,
+    duration: Duration,
+    easing: Easing,
+) -> Animation
+where
+    T: Interpolate + 'static,
+{
+    signal.set(from.clone());
+    tween(clock, signal.clone(), from, to, duration, easing)
+}
+
+/// Starts writing from the next frame.
+pub(super) fn tween<T>(
+    clock: &FrameClock,
+    signal: WriteSignal<T>,
+    from: T,
+    to: T,
+    duration: Duration,
+    easing: Easing,
+) -> Animation
+where
+    T: Interpolate + 'static,
+{
+    let animation = Animation::new();
+    let mut elapsed = Duration::ZERO;
+    let handle = animation.clone();
+    clock.add_ticker(move |interval| {
+        if !handle.is_running() {
+            return false;
+        }
+
+        elapsed = elapsed.saturating_add(interval);
+        if elapsed >= duration {
+            signal.set(to.clone());
+            handle.finish();
+            return false;
+        }
+
+        let t = elapsed.as_secs_f32() / duration.as_secs_f32();
+        signal.set(from.interpolate(&to, easing.apply(t)));
+        true
+    });
+
+    animation
+}
@@ -9,16 +9,18 @@ use irisia_backend::{
 
 use crate::{
     Result,
+    animation::FrameClock,
     event::{EventDispatcher, standard::WindowDestroyed},
     model::{EleModel, ModelCreateCtx, VNode},
     prim_element::{
-        EMCreateCtx, EmitEventArgs, RenderTreeExt, callback_queue::CallbackQueue,
-        layout::LayoutInput,
-    },
-    primitive::{
-        Point, Region,
-        length::{LengthStandard, LengthStandardGlobalPart},
+        EMCreateCtx, EmitEventArgs, RenderTreeExt,
+        callback_queue::CallbackQueue,
+        image::ImageCache,
+        layout::{Direction, LayoutInput},
+        overlay::OverlayLayer,
+        positioned::ContainingBlocks,
     },
+    primitive::{Point, Region, length::LengthStandardGlobalPart},
 };
 
 use super::{
@@ -45,17 +47,16 @@ impl AppWindow for BackendRuntime {
         interval: Duration,
         window_inner_size: PhysicalSize<u32>,
     ) -> Result<()> {
-        let redraw_root_inputs = if self.window_resized {
+        let full_reflow = self.gc.redraw_scheduler.take_full_reflow_request();
+        let redraw_root_inputs = if self.window_resized || full_reflow {
             let mut lsgp = self.gc.length_standard_global_part();
             lsgp.viewport_size = window_inner_size.into();
             self.gc.length_standard.set(lsgp);
 
             Some(LayoutInput {
                 constraint: window_size_to_constraint(window_inner_size),
-                length_standard: lsgp.viewport_size.map(|x| LengthStandard {
-                    global: lsgp,
-                    percentage_reference: x as f32,
-                }),
+                length_standard: lsgp.viewport_size.map(|x| lsgp.root_standard(x as f32)),
+                direction: Direction::Ltr,
             })
         } else {
             None
@@ -66,6 +67,9 @@ impl AppWindow for BackendRuntime {
             interval,
             &self.root_model.get_element().0,
             redraw_root_inputs,
+            &self.gc.frame_clock,
+            &self.gc.overlay,
+            self.gc.length_standard_global_part(),
         );
 
         self.window_resized = false;
@@ -73,11 +77,13 @@ impl AppWindow for BackendRuntime {
     }
 
     fn on_window_event(&mut self, event: WindowEvent, _window_inner_size: PhysicalSize<u32>) {
-        // TODO: watch dpi change
-        // if let WindowEvent::ScaleFactorChanged { scale_factor, inner_size_writer }
-
-        if let WindowEvent::Resized(_) = event {
-            self.window_resized = true;
+        match &event {
+            WindowEvent::Resized(_) => self.window_resized = true,
+            // the suggested inner size is kept, `Resized` follows if it changes
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                self.gc.set_dpi(*scale_factor as f32)
+            }
+            _ => {}
         }
 
         let Some(next) = self.pointer_state.next(&event) else {
@@ -89,17 +95,23 @@ impl AppWindow for BackendRuntime {
             prev: self.pointer_state,
             next,
             cursor_may_over: true,
+            blocked: false,
         };
         self.pointer_state = next;
 
+        let mut args = EmitEventArgs {
+            queue: &mut self.callback_queue,
+            delta,
+            clips: Vec::new(),
+        };
+
+        // the overlay is above the root
+        self.gc.overlay.emit_event(&mut args);
         self.root_model
             .get_element()
             .0
             .borrow_mut()
-            .emit_event(&mut EmitEventArgs {
-                queue: &mut self.callback_queue,
-                delta,
-            });
+            .emit_event(&mut args);
         self.callback_queue.execute();
         // TODO
         // if let WindowEvent::Resized(size) = &event {
@@ -144,6 +156,7 @@ where
 
         move |window: Arc<WinitWindow>, close_handle| {
             let redraw_scheduler = RedrawScheduler::new(window.clone());
+            let frame_clock = FrameClock::new(window.clone());
 
             let gc = Rc::new(GlobalContent {
                 global_ed: ev_disp,
@@ -151,9 +164,14 @@ where
                 length_standard: Cell::new(LengthStandardGlobalPart {
                     viewport_size: window.inner_size().into(),
                     dpi: window.scale_factor() as _,
+                    root_font_size: LengthStandardGlobalPart::DEFAULT_ROOT_FONT_SIZE,
                 }),
                 window,
                 redraw_scheduler,
+                image_cache: ImageCache::new(),
+                frame_clock,
+                containing_blocks: ContainingBlocks::new(),
+                overlay: OverlayLayer::new(),
                 close_handle,
                 user_close: Cell::new(true),
             });
@@ -161,6 +179,7 @@ where
             let root_model = root_creator().create(&ModelCreateCtx::create_as_root(EMCreateCtx {
                 global_content: gc.clone(),
                 parent: None,
+                paint_order_dirty: None,
             }));
 
             //root.set_draw_region(Some(window_size_to_draw_region(gc.window().inner_size())));
@@ -3,8 +3,12 @@ use std::{cell::Cell, sync::Arc};
 use irisia_backend::{WinitWindow, window_handle::CloseHandle};
 
 use crate::{
+    animation::FrameClock,
     event::EventDispatcher,
-    prim_element::{Element, RenderTree, WeakElement},
+    prim_element::{
+        Element, RenderTree, WeakElement, image::ImageCache, overlay::OverlayLayer,
+        positioned::ContainingBlocks,
+    },
     primitive::length::LengthStandardGlobalPart,
 };
 
@@ -18,6 +22,10 @@ pub struct GlobalContent {
     pub(super) close_handle: CloseHandle,
     pub(super) user_close: Cell<bool>,
     pub(super) redraw_scheduler: RedrawScheduler,
+    pub(super) image_cache: ImageCache,
+    pub(super) frame_clock: FrameClock,
+    pub(super) containing_blocks: ContainingBlocks,
+    pub(super) overlay: OverlayLayer,
 }
 
 impl GlobalContent {
@@ -57,10 +65,51 @@ impl GlobalContent {
         &self.window
     }
 
+    /// Returns the decoded images shared by this window
+    pub fn image_cache(&self) -> &ImageCache {
+        &self.image_cache
+    }
+
+    /// Returns the clock driving the animations of this window
+    pub fn frame_clock(&self) -> &FrameClock {
+        &self.frame_clock
+    }
+
+    pub(crate) fn containing_blocks(&self) -> &ContainingBlocks {
+        &self.containing_blocks
+    }
+
+    pub(crate) fn overlay(&self) -> &OverlayLayer {
+        &self.overlay
+    }
+
     pub fn length_standard_global_part(&self) -> LengthStandardGlobalPart {
         self.length_standard.get()
     }
 
+    /// Set the font size of the roots in logical pixels, which `rem` refers to.
+    /// The whole window is laid out again.
+    pub fn set_root_font_size(&self, size: f32) {
+        let mut lsgp = self.length_standard.get();
+        if lsgp.root_font_size != size {
+            lsgp.root_font_size = size;
+            self.length_standard.set(lsgp);
+            self.redraw_scheduler.request_full_reflow();
+        }
+    }
+
+    /// Update the scale factor of the window. Cached layouts are keyed by the
+    /// length standard which includes it, so the full reflow recomputes all of
+    /// them, and paragraphs are rebuilt at the new scale.
+    pub(super) fn set_dpi(&self, dpi: f32) {
+        let mut lsgp = self.length_standard.get();
+        if lsgp.dpi != dpi {
+            lsgp.dpi = dpi;
+            self.length_standard.set(lsgp);
+            self.redraw_scheduler.request_full_reflow();
+        }
+    }
+
     pub fn user_close(&self) -> bool {
         self.user_close.get()
     }
@@ -1,4 +1,4 @@
-use crate::primitive::{Point, Region};
+use crate::primitive::Point;
 pub(crate) use state::PointerState;
 
 mod state;
@@ -31,19 +31,24 @@ pub struct PointerStateDelta {
     pub(crate) prev: PointerState,
     pub(crate) next: PointerState,
     pub(crate) cursor_may_over: bool,
+    /// A layer above is hit or modal, nothing here is hit and the elements
+    /// only see the pointer leave.
+    pub(crate) blocked: bool,
 }
 
 impl PointerStateDelta {
+    /// `hit_test` tells whether a point in window coordinates is on the element.
     pub fn get_event(
         &mut self,
-        draw_region: Region,
+        hit_test: &dyn Fn(Point) -> bool,
         prev_cursor_directly_over: &mut bool,
     ) -> impl Iterator<Item = PointerEvent> + use<> {
         let (iter, cursor_over) = self.prev.compare(
             &self.next,
-            draw_region,
+            hit_test,
             *prev_cursor_directly_over,
             self.cursor_may_over,
+            self.blocked,
         );
 
         *prev_cursor_directly_over = cursor_over;
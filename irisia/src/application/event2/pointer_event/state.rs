@@ -1,7 +1,7 @@
 use irisia_backend::winit::event::{ElementState, MouseButton, Touch, TouchPhase, WindowEvent};
 use smallvec::SmallVec;
 
-use crate::primitive::{Point, Region};
+use crate::primitive::Point;
 
 use super::PointerEvent;
 
@@ -79,9 +79,9 @@ impl PointerState {
         })
     }
 
-    fn pointer_inside(&self, region: Region) -> bool {
+    fn pointer_inside(&self, hit_test: &dyn Fn(Point) -> bool) -> bool {
         match self.cursor_position {
-            Some(pos) => region.contains_point(pos),
+            Some(pos) => hit_test(pos),
             None => false,
         }
     }
@@ -89,14 +89,15 @@ impl PointerState {
     pub(super) fn compare(
         &self,
         next: &Self,
-        draw_region: Region,
+        hit_test: &dyn Fn(Point) -> bool,
         prev_cursor_over: bool,
         next_may_cursor_over: bool,
+        blocked: bool,
     ) -> (impl Iterator<Item = PointerEvent> + use<>, bool) {
         let mut events: SmallVec<[PointerEvent; 4]> = SmallVec::new();
         let inside_draw_region = (
-            self.pointer_inside(draw_region),
-            next.pointer_inside(draw_region),
+            self.pointer_inside(hit_test),
+            !blocked && next.pointer_inside(hit_test),
         );
 
         let cursor_over = (
@@ -13,10 +13,12 @@ use reflow::ReflowScheduler;
 
 use crate::{
     WeakHandle,
+    animation::FrameClock,
     prim_element::{
         Element, RenderArgs, RenderTree, RenderTreeExt, WeakElement, layout::LayoutInput,
+        overlay::OverlayLayer,
     },
-    primitive::Point,
+    primitive::{Point, length::LengthStandardGlobalPart},
 };
 
 mod reflow;
@@ -24,6 +26,7 @@ mod reflow;
 pub(super) struct RedrawScheduler {
     window: Arc<WinitWindow>,
     redraw_req_sent: Cell<bool>,
+    full_reflow_req: Cell<bool>,
     reflow_nodes: RefCell<ReflowScheduler>,
     repaint_nodes: RefCell<HashMap<*const (), WeakHandle<dyn RenderTree>>>,
 }
@@ -33,6 +36,7 @@ impl RedrawScheduler {
         Self {
             window,
             redraw_req_sent: Cell::new(false),
+            full_reflow_req: Cell::new(false),
             reflow_nodes: RefCell::new(ReflowScheduler::new()),
             repaint_nodes: Default::default(),
         }
@@ -45,6 +49,17 @@ impl RedrawScheduler {
         }
     }
 
+    /// Lay out the whole window in next frame, for changes of the global part
+    /// of the length standard.
+    pub fn request_full_reflow(&self) {
+        self.request_window_redraw();
+        self.full_reflow_req.set(true);
+    }
+
+    pub fn take_full_reflow_request(&self) -> bool {
+        self.full_reflow_req.replace(false)
+    }
+
     pub fn request_reflow(&self, el: &WeakElement) {
         self.request_window_redraw();
 
@@ -59,24 +74,39 @@ impl RedrawScheduler {
             .insert(el.as_ptr() as _, el.clone());
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn redraw(
         &self,
         canvas: &Canvas,
         interval: Duration,
         root: &Element,
         redraw_root: Option<LayoutInput>,
+        frame_clock: &FrameClock,
+        overlay: &OverlayLayer,
+        global: LengthStandardGlobalPart,
     ) {
         self.redraw_req_sent.set(true); // prevent request next frame redraw
 
+        // changes made by animations are drawn in this frame
+        let animating = frame_clock.tick(interval);
+
         let dirty_region = if let Some(redraw_root_inputs) = redraw_root {
             self.reflow_nodes.borrow_mut().clear();
-            self.repaint_nodes.borrow_mut().clear();
 
-            let mut root_ref = root.borrow_mut();
-            root_ref.force_compute_layout_cached(redraw_root_inputs);
+            root.borrow_mut()
+                .force_compute_layout_cached(redraw_root_inputs);
+            overlay.layout(global, true);
+            overlay.update_locations(global);
+            overlay.take_removed_regions();
+
+            self.repaint_nodes.borrow_mut().clear();
             None
         } else {
-            let dirty_region = self.perform_partial_reflow();
+            self.perform_partial_reflow();
+            overlay.layout(global, false);
+            overlay.update_locations(global);
+
+            let dirty_region = self.collect_dirty_region(overlay);
             canvas.save();
             canvas.clip_region(&dirty_region, ClipOp::Intersect);
             Some(dirty_region)
@@ -85,29 +115,42 @@ impl RedrawScheduler {
         self.redraw_req_sent.set(false);
 
         canvas.clear(Color::WHITE);
-        root.borrow_mut().render_entry(
-            RenderArgs {
-                canvas,
-                interval,
-                dirty_region: dirty_region.as_ref(),
-            },
-            Point::ZERO,
-        );
+        let args = RenderArgs {
+            canvas,
+            interval,
+            dirty_region: dirty_region.as_ref(),
+            containing_block: Point::ZERO,
+        };
+        root.borrow_mut().render_entry(args, Point::ZERO);
+        for content in overlay.elements() {
+            content.borrow_mut().render_entry(args, Point::ZERO);
+        }
 
         if dirty_region.is_some() {
             canvas.restore();
         }
+
+        // anchors may be drawn elsewhere in this frame, next frame follows them
+        // and paints nothing if they didn't move
+        let painted = dirty_region
+            .as_ref()
+            .is_none_or(|region| !region.is_empty());
+        if animating || (painted && overlay.has_anchors()) {
+            self.request_window_redraw();
+        }
+    }
+
+    fn perform_partial_reflow(&self) {
+        // taken out first, layouts may request reflows again
+        let elements = self.reflow_nodes.borrow_mut().take();
+        reflow::reflow(elements);
     }
 
-    fn perform_partial_reflow(&self) -> SkRegion {
+    fn collect_dirty_region(&self, overlay: &OverlayLayer) -> SkRegion {
         let mut dirty_region = SkRegion::new();
 
-        for reflow_node in self.reflow_nodes.borrow_mut().get_reflow_roots() {
-            let mut node = reflow_node.borrow_mut();
-            let layout_input = node.common().layout_input.expect(
-                "this element has not been layouted before. its parent must reflow but it didn't.",
-            );
-            node.force_compute_layout_cached(layout_input);
+        for region in overlay.take_removed_regions() {
+            dirty_region.op_rect(region.round_to_skia_irect(), RegionOp::Union);
         }
 
         for repaint_node in self
@@ -122,13 +165,9 @@ impl RedrawScheduler {
                 dirty_region.op_rect(prev_draw_region.round_to_skia_irect(), RegionOp::Union);
             }
 
-            dirty_region.op_rect(
-                node.common_mut()
-                    .layout_output
-                    .as_rect()
-                    .round_to_skia_irect(),
-                RegionOp::Union,
-            );
+            if let Some(region) = node.common().device_region() {
+                dirty_region.op_rect(region.round_to_skia_irect(), RegionOp::Union);
+            }
         }
 
         dirty_region
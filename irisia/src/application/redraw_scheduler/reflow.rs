@@ -1,142 +1,266 @@
 use std::{
-    collections::HashMap,
-    rc::{Rc, Weak},
+    collections::{BinaryHeap, HashMap},
+    rc::Rc,
 };
 
-use crate::{
-    prim_element::{Element, WeakElement, layout::SpaceConstraint},
-    primitive::Size,
+use crate::prim_element::{
+    Element, RenderTreeExt, WeakElement, layout::Measurements, positioned::same_out_of_flow,
 };
 
+/// Elements whose layouts may have changed. They are laid out again with their
+/// last inputs, the deepest first, and the parent follows only if it may be
+/// affected, so a change stops at the first ancestor keeping its size.
 pub(super) struct ReflowScheduler {
-    pool: HashMap<*const (), ReflowNode>,
-    key_list: Vec<*const ()>,
-    // first_certain: Option<*const ()>,
-}
-
-struct ReflowNode {
-    el: WeakElement,
+    pool: HashMap<*const (), WeakElement>,
 }
 
 impl ReflowScheduler {
     pub fn new() -> Self {
         Self {
             pool: HashMap::new(),
-            key_list: Vec::new(),
         }
     }
 
-    fn load_keys(&mut self) {
-        self.key_list.clear();
-        self.key_list.extend(self.pool.keys());
-    }
-
     pub fn insert(&mut self, el: &WeakElement) {
-        self.pool
-            .insert(el.as_ptr() as _, ReflowNode { el: el.clone() });
+        self.pool.insert(el.as_ptr() as _, el.clone());
     }
 
-    pub fn get_reflow_roots(&mut self) -> impl Iterator<Item = Element> {
-        self.find_fixed_size_ancestors();
-        self.remove_duplicated();
-
+    /// Take the requested elements still alive.
+    pub fn take(&mut self) -> Vec<Element> {
         self.pool
             .drain()
-            .map(|(_, node)| node.el.upgrade().unwrap())
-    }
-
-    fn find_fixed_size_ancestors(&mut self) {
-        self.load_keys();
-
-        for key in self.key_list.iter() {
-            let origin_node = match self.pool[key].el.upgrade() {
-                Some(rc) => rc,
-                None => {
-                    self.pool.remove(key);
-                    continue;
-                }
-            };
-
-            let origin_node_key = get_key(&origin_node);
-            let mut current_child_node = origin_node;
-
-            let mut current_is_self = true;
-            loop {
-                if is_fixed_size(
-                    &current_child_node
-                        .borrow()
-                        .common()
-                        .layout_input
-                        .unwrap()
-                        .constraint,
-                ) {
-                    if !current_is_self {
-                        self.pool.remove(&origin_node_key);
-                        self.pool.insert(
-                            get_key(&current_child_node),
-                            ReflowNode {
-                                el: Rc::downgrade(&current_child_node),
-                            },
-                        );
-                    }
-                    break;
-                }
-
-                current_is_self = false;
-
-                if let Some(parent) = get_parent(&current_child_node) {
-                    current_child_node = parent;
-                }
+            .filter_map(|(_, el)| el.upgrade())
+            .collect()
+    }
+
+    /// Drop the requests when the whole tree is laid out anyway. The requesting
+    /// elements and their ancestors are invalidated still, or the unchanged
+    /// inputs would hit the caches.
+    pub fn clear(&mut self) {
+        for el in self.take() {
+            let mut node = Some(el);
+            while let Some(el) = node {
+                el.invalidate();
+                node = el.parent();
             }
         }
     }
+}
 
-    fn remove_duplicated(&mut self) {
-        self.load_keys();
-
-        'next_node: for key in self.key_list.iter() {
-            let mut current_child_node = match self.pool[key].el.upgrade() {
-                Some(rc) => rc,
-                None => {
-                    self.pool.remove(key);
-                    continue;
-                }
-            };
-
-            while let Some(parent_node) = get_parent(&current_child_node) {
-                if self.pool.contains_key(&get_key(&parent_node)) {
-                    self.pool.remove(key);
-                    continue 'next_node;
-                }
-                current_child_node = parent_node;
-            }
+/// Lay out `elements` again, see [`ReflowScheduler`].
+pub(super) fn reflow<N: ReflowNode>(elements: Vec<N>) {
+    let mut queue = ReflowQueue::default();
+    for el in elements {
+        queue.push(el);
+    }
+
+    while let Some(Pending { node, measurements }) = queue.pop() {
+        let Some(changed) = node.relayout() else {
+            // never laid out, its parent lays it out the first time
+            continue;
+        };
+
+        // a parent which only laid it out depends on its size alone, one which
+        // measured it depends on the measurements too. the roots of the window
+        // and the overlay have no parent
+        if !changed && !node.measurements_changed(&measurements) {
+            continue;
+        }
+        if let Some(parent) = node.parent() {
+            queue.push(parent);
         }
     }
+}
 
-    pub fn clear(&mut self) {
-        self.pool.clear();
+/// A node of the tree to reflow, implemented by [`Element`].
+pub(super) trait ReflowNode: Sized {
+    /// What the parent measured of the node before it is invalidated.
+    type Measurements;
+
+    fn key(&self) -> *const ();
+
+    fn parent(&self) -> Option<Self>;
+
+    /// Run the final layout again next time, returns the forgotten measurements.
+    fn invalidate(&self) -> Self::Measurements;
+
+    /// Lay out again with the last input, returns whether the size or anything
+    /// else passed to the parent changed. `None` if never laid out.
+    fn relayout(&self) -> Option<bool>;
+
+    /// Measure again under the inputs of `old`, returns whether a size differs.
+    fn measurements_changed(&self, old: &Self::Measurements) -> bool;
+}
+
+impl ReflowNode for Element {
+    type Measurements = Measurements;
+
+    fn key(&self) -> *const () {
+        Rc::as_ptr(self) as _
+    }
+
+    fn parent(&self) -> Option<Self> {
+        self.borrow()
+            .common()
+            .ctx
+            .parent
+            .as_ref()
+            .map(|weak| weak.upgrade().unwrap() as _)
+    }
+
+    fn invalidate(&self) -> Measurements {
+        self.borrow_mut().common_mut().layout_cache.invalidate()
+    }
+
+    fn relayout(&self) -> Option<bool> {
+        let mut el = self.borrow_mut();
+        let layout_input = el.common().layout_input()?;
+
+        let old_size = el.common().layout_output.size;
+        let old_out_of_flow = el.common().out_of_flow.clone();
+        let new_size = el.force_compute_layout_cached(layout_input);
+        // the parent passes them on up to their containing block
+        let out_of_flow_changed = !same_out_of_flow(&old_out_of_flow, &el.common().out_of_flow);
+        Some(new_size != old_size || out_of_flow_changed)
+    }
+
+    fn measurements_changed(&self, old: &Measurements) -> bool {
+        // cached again, so the parent gets them for free
+        let mut el = self.borrow_mut();
+        old.iter()
+            .flatten()
+            .any(|&(inputs, size)| el.measure_cached(inputs) != size)
     }
 }
 
-fn is_fixed_size(constraint: &Size<SpaceConstraint>) -> bool {
-    matches!(
-        constraint,
-        Size {
-            width: SpaceConstraint::Exact(_),
-            height: SpaceConstraint::Exact(_)
+struct Pending<N: ReflowNode> {
+    node: N,
+    /// Measurements by the parent, forgotten when invalidated.
+    measurements: N::Measurements,
+}
+
+/// Pending nodes, the deepest first so each is laid out once after its
+/// changed descendants.
+struct ReflowQueue<N: ReflowNode> {
+    order: BinaryHeap<(usize, *const ())>,
+    pending: HashMap<*const (), Pending<N>>,
+}
+
+impl<N: ReflowNode> Default for ReflowQueue<N> {
+    fn default() -> Self {
+        Self {
+            order: BinaryHeap::new(),
+            pending: HashMap::new(),
         }
-    )
+    }
 }
 
-fn get_key(node: &Element) -> *const () {
-    Rc::as_ptr(node) as _
+impl<N: ReflowNode> ReflowQueue<N> {
+    fn push(&mut self, node: N) {
+        let key = node.key();
+        if self.pending.contains_key(&key) {
+            // already invalidated, and not measured since
+            return;
+        }
+
+        let measurements = node.invalidate();
+        self.order.push((depth(&node), key));
+        self.pending.insert(key, Pending { node, measurements });
+    }
+
+    fn pop(&mut self) -> Option<Pending<N>> {
+        let (_, key) = self.order.pop()?;
+        self.pending.remove(&key)
+    }
+}
+
+fn depth<N: ReflowNode>(node: &N) -> usize {
+    let mut depth = 0;
+    let mut node = node.parent();
+    while let Some(parent) = node {
+        depth += 1;
+        node = parent.parent();
+    }
+    depth
 }
 
-pub fn get_parent(node: &Element) -> Option<Element> {
-    node.borrow()
-        .common()
-        .ctx
-        .parent
-        .as_ref()
-        .map(|weak| weak.upgrade().unwrap() as _)
+#[test]
+fn test_reflow() {
+    use std::cell::{Cell, RefCell};
+
+    struct Node {
+        name: &'static str,
+        parent: Option<Rc<Node>>,
+        /// Size of the last layout and the next one.
+        size: Cell<f32>,
+        next_size: Cell<f32>,
+        /// Cached measurement of the parent and the one measured now.
+        measured: Cell<Option<f32>>,
+        next_measured: Cell<f32>,
+        log: Rc<RefCell<Vec<&'static str>>>,
+    }
+
+    impl ReflowNode for Rc<Node> {
+        type Measurements = Option<f32>;
+
+        fn key(&self) -> *const () {
+            Rc::as_ptr(self) as _
+        }
+
+        fn parent(&self) -> Option<Self> {
+            self.parent.clone()
+        }
+
+        fn invalidate(&self) -> Option<f32> {
+            self.measured.take()
+        }
+
+        fn relayout(&self) -> Option<bool> {
+            self.log.borrow_mut().push(self.name);
+            Some(self.size.replace(self.next_size.get()) != self.next_size.get())
+        }
+
+        fn measurements_changed(&self, old: &Option<f32>) -> bool {
+            old.is_some_and(|old| old != self.next_measured.get())
+        }
+    }
+
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let node = |name, parent: Option<&Rc<Node>>| {
+        Rc::new(Node {
+            name,
+            parent: parent.cloned(),
+            size: Cell::new(1.0),
+            next_size: Cell::new(1.0),
+            measured: Cell::new(None),
+            next_measured: Cell::new(1.0),
+            log: log.clone(),
+        })
+    };
+    let root = node("root", None);
+    let a = node("a", Some(&root));
+    let b = node("b", Some(&a));
+    let c = node("c", Some(&root));
+
+    // the deepest first, and each once. the root stops though its size changed
+    root.next_size.set(2.0);
+    b.next_size.set(2.0);
+    reflow(vec![root.clone(), c.clone(), b.clone()]);
+    let laid_out = log.take();
+    assert_eq!(laid_out.len(), 4);
+    assert_eq!(laid_out[0], "b");
+    assert_eq!(laid_out[3], "root");
+
+    // `a` keeps its size and measurement, the parent is not affected
+    b.next_size.set(3.0);
+    a.measured.set(Some(1.0));
+    reflow(vec![b.clone()]);
+    assert_eq!(log.take(), ["b", "a"]);
+
+    // `a` keeps its size but the measurement by the parent changed
+    b.next_size.set(4.0);
+    a.measured.set(Some(1.0));
+    a.next_measured.set(5.0);
+    reflow(vec![b.clone()]);
+    assert_eq!(log.take(), ["b", "a", "root"]);
 }
@@ -6,13 +6,13 @@ type Core = Inner<dyn Fn(CallbackAction) -> bool>;
 
 pub struct Listener(Weak<Core>);
 
-pub(crate) struct StrongListener(#[allow(dead_code)] Rc<Core>);
+pub(crate) struct StrongListener(Rc<Core>);
 
-// impl StrongListener {
-//     pub fn downgrade(&self) -> Listener {
-//         Listener(Rc::downgrade(&self.0))
-//     }
-// }
+impl StrongListener {
+    pub fn downgrade(&self) -> Listener {
+        Listener(Rc::downgrade(&self.0))
+    }
+}
 
 impl Listener {
     /// The callback ***must NOT capture hooks*** or will cause underlying memory leaks
@@ -29,6 +29,10 @@ impl Listener {
         StrongListener(inner)
     }
 
+    pub(crate) fn is_alive(&self) -> bool {
+        self.0.strong_count() != 0
+    }
+
     pub(crate) fn callback(&self, action: CallbackAction) -> bool {
         let Some(rc) = self.0.upgrade() else {
             return false;
@@ -1,9 +1,11 @@
 use std::rc::Rc;
+pub(crate) use tracking::track;
 pub use {listener::Listener, signal::Signal};
 
 pub mod listener;
 pub mod signal;
 pub mod signal_group;
+mod tracking;
 pub mod utils;
 pub mod watcher;
 
@@ -2,6 +2,7 @@ use smallvec::SmallVec;
 
 use crate::hook::{
     listener::StrongListener,
+    tracking,
     utils::{trace_cell::TraceRef, CallbackAction, DirtyCount, ListenerList, TraceCell},
     Listener,
 };
@@ -20,6 +21,9 @@ where
     T: ?Sized,
 {
     pub fn read(&self) -> TraceRef<T> {
+        if let Some(listener) = tracking::record_read(self as *const Self as *const ()) {
+            self.dependent(listener);
+        }
         self.value.borrow().unwrap()
     }
 
@@ -0,0 +1,90 @@
+use std::{cell::RefCell, collections::HashSet};
+
+use super::Listener;
+
+thread_local! {
+    static TRACKING: RefCell<Option<Tracking>> = const { RefCell::new(None) };
+}
+
+struct Tracking {
+    listener: Listener,
+    signals: HashSet<*const ()>,
+}
+
+/// Calls `f` and makes `listener` a dependent of every signal read inside it,
+/// for consumers whose dependencies are not known ahead, like a paint callback.
+/// Scopes can be nested, reads only go to the innermost one.
+pub(crate) fn track<R>(listener: Listener, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<Tracking>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            TRACKING.set(self.0.take());
+        }
+    }
+
+    let _restore = Restore(TRACKING.replace(Some(Tracking {
+        listener,
+        signals: HashSet::new(),
+    })));
+
+    f()
+}
+
+/// Returns the listener of the current scope if the signal at `addr` has not
+/// been read in it yet.
+pub(super) fn record_read(addr: *const ()) -> Option<Listener> {
+    TRACKING.with_borrow_mut(|tracking| {
+        let tracking = tracking.as_mut()?;
+        tracking
+            .signals
+            .insert(addr)
+            .then(|| tracking.listener.clone())
+    })
+}
+
+#[test]
+fn test_track() {
+    use std::{cell::Cell, rc::Rc};
+
+    use super::{Signal, utils::CallbackAction};
+
+    let updates = Rc::new(Cell::new(0));
+    let listener = {
+        let updates = updates.clone();
+        Listener::new(|_| {
+            move |action: CallbackAction| {
+                if action.is_update() {
+                    updates.set(updates.get() + 1);
+                }
+                true
+            }
+        })
+    };
+
+    let read = Signal::state(1);
+    let read_twice = Signal::state(2);
+    let inner_read = Signal::state(3);
+    let not_read = Signal::state(4);
+
+    track(listener.downgrade(), || {
+        let _ = *read.read();
+        let _ = *read_twice.read() + *read_twice.read();
+        track(Listener::new(|_| |_| true).downgrade(), || {
+            let _ = *inner_read.read();
+        });
+    });
+    let _ = *not_read.read();
+
+    read.set(10);
+    assert_eq!(updates.get(), 1);
+    read_twice.set(20);
+    assert_eq!(updates.get(), 2);
+    inner_read.set(30);
+    not_read.set(40);
+    assert_eq!(updates.get(), 2);
+
+    drop(listener);
+    read.set(100);
+    assert_eq!(updates.get(), 2);
+}
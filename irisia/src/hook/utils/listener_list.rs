@@ -17,6 +17,9 @@ impl ListenerList {
 
     pub fn add_listener(&self, listener: Listener) {
         match self.listeners.try_borrow_mut() { Ok(mut refmut) => {
+            // listeners replaced on every use (like the dependencies of a paint
+            // callback) would pile up until the next callback otherwise
+            refmut.retain(Listener::is_alive);
             refmut.push(listener);
         } _ => {
             self.delay_add.borrow_mut().push(listener);
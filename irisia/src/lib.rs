@@ -12,6 +12,7 @@ macro_rules! inner_error {
     };
 }
 
+pub mod animation;
 pub mod application;
 pub mod event;
 pub mod hook;
@@ -1,21 +1,21 @@
 #[macro_export]
 macro_rules! info {
     ($($tt:tt)+) => {
-        $crate::log::println!("INFO", $($tt)*);
+        $crate::println!("INFO", $($tt)*);
     };
 }
 
 #[macro_export]
 macro_rules! warning {
     ($($tt:tt)+) => {
-        $crate::log::println!("WARNING", $($tt)*);
+        $crate::println!("WARNING", $($tt)*);
     };
 }
 
 #[macro_export]
 macro_rules! error {
     ($($tt:tt)+) => {
-        $crate::log::println!("ERROR", $($tt)*);
+        $crate::println!("ERROR", $($tt)*);
     };
 }
 
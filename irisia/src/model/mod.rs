@@ -39,6 +39,11 @@ pub struct ModelCreateCtx {
 }
 
 impl ModelCreateCtx {
+    /// Context for creating the element of a primitive model.
+    pub fn element_ctx(&self) -> &EMCreateCtx {
+        &self.el_ctx
+    }
+
     pub(crate) fn create_as_root(ctx: EMCreateCtx) -> Self {
         Self {
             el_ctx: ctx,
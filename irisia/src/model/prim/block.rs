@@ -1,4 +1,7 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 
 use crate::{
     WeakHandle,
@@ -98,10 +101,12 @@ impl<Cd: 'static> BlockModel<Cd> {
         let mut children_ctx = None;
 
         let prim_block = Rc::new_cyclic(|render_block_weak| {
+            let paint_order_dirty = Rc::new(Cell::new(true));
             let ctx = ModelCreateCtx {
                 el_ctx: EMCreateCtx {
                     global_content: el_ctx.global_content.clone(),
                     parent: Some(render_block_weak.clone() as _),
+                    paint_order_dirty: Some(paint_order_dirty.clone()),
                 },
                 parent: Some(self_weak.clone() as _),
             };
@@ -115,6 +120,7 @@ impl<Cd: 'static> BlockModel<Cd> {
                 this: render_block_weak.clone() as _,
                 style: props.style.clone(),
                 children: visit_into_list(&children),
+                paint_order_dirty,
                 layouter: props.display.clone(),
                 event_callback: props.on.clone(),
                 ctx: el_ctx,
@@ -0,0 +1,88 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    hook::{
+        Signal,
+        watcher::{WatcherGuard, WatcherList},
+    },
+    model::{EleModel, Model, ModelCreateCtx, VModel, VNode, component::Component},
+    prim_element::{
+        Element, EventCallback,
+        canvas::{PaintFn, RenderCanvas},
+    },
+    primitive::{Length, Size},
+};
+
+use super::{PrimitiveModel, PrimitiveVnodeWrapper, panic_when_call_unreachable};
+
+/// Draws custom content with skia. `paint` is called on every repaint of the
+/// element, which happens when `paint` itself or any signal read inside it
+/// changes.
+#[derive(Default)]
+pub struct Canvas {
+    pub paint: Option<Signal<PaintFn>>,
+    /// Size used by layouters, auto means zero.
+    pub size: Option<Signal<Size<Length>>>,
+    pub on: Option<EventCallback>,
+}
+
+impl Component for Canvas {
+    fn create(self, _watcher_list: &mut WatcherList) -> impl VNode<()> + use<> {
+        PrimitiveVnodeWrapper(self)
+    }
+}
+
+impl VModel<()> for PrimitiveVnodeWrapper<Canvas> {
+    type Storage = PrimitiveModel<CanvasModel>;
+
+    fn create(&self, ctx: &ModelCreateCtx) -> Self::Storage {
+        let model = Rc::new(RefCell::new(CanvasModel {
+            el: Rc::new_cyclic(|weak| {
+                RefCell::new(RenderCanvas::new(
+                    weak.clone() as _,
+                    self.0.paint.clone(),
+                    self.0.size.clone(),
+                    self.0.on.clone(),
+                    &ctx.el_ctx,
+                ))
+            }),
+        }));
+
+        let mut wl = WatcherList::new();
+        wl.watch_borrow_mut(
+            &model,
+            |this, _| this.el.borrow_mut().paint_updated(),
+            self.0.paint.clone(),
+        )
+        .watch_borrow_mut(
+            &model,
+            |this, _| this.el.borrow_mut().preferred_size_updated(),
+            self.0.size.clone(),
+        );
+
+        PrimitiveModel {
+            _watcher_list: wl,
+            model,
+        }
+    }
+
+    fn update(&self, _: &mut Self::Storage, _: &ModelCreateCtx) {
+        panic_when_call_unreachable()
+    }
+}
+
+pub struct CanvasModel {
+    el: Rc<RefCell<RenderCanvas>>,
+}
+
+impl EleModel<()> for CanvasModel {
+    fn get_element(&self) -> (Element, ()) {
+        (self.el.clone(), ())
+    }
+}
+
+impl Model<()> for CanvasModel {
+    fn visit(&self, f: &mut dyn FnMut(Element, ())) {
+        f(self.el.clone(), ())
+    }
+}
@@ -1,53 +1,74 @@
 use std::{cell::RefCell, rc::Rc};
 
 use crate::{
-    hook::{reactive::Reactive, Signal},
-    model::{component::Component, EleModel, Model, ModelCreateCtx, VModel, VNode},
+    hook::{
+        Signal,
+        watcher::{WatcherGuard, WatcherList},
+    },
+    model::{EleModel, Model, ModelCreateCtx, VModel, VNode, component::Component},
     prim_element::{
-        image::{ImageStyle, RenderRect},
         Element, EventCallback,
+        image::{ImageSource, ImageStyle, InitRenderImage, RenderImage},
     },
 };
 
-use super::{panic_when_call_unreachable, read_or_default, PrimitiveVnodeWrapper};
+use super::{PrimitiveModel, PrimitiveVnodeWrapper, panic_when_call_unreachable};
 
 #[derive(Default)]
-pub struct Rect {
+pub struct Image {
+    pub source: Option<Signal<ImageSource>>,
+    /// Shown while `source` is loading.
+    pub placeholder: Option<Signal<ImageSource>>,
+    /// Shown if `source` failed to load or decode.
+    pub fallback: Option<Signal<ImageSource>>,
     pub style: Option<Signal<ImageStyle>>,
     pub on: Option<EventCallback>,
 }
 
-impl Component for Rect {
-    type Created = ();
-    type ChildProps = ();
-
-    fn create(self) -> (Self::Created, impl VNode<ParentProps = ()>) {
-        ((), PrimitiveVnodeWrapper(self))
+impl Component for Image {
+    fn create(self, _watcher_list: &mut WatcherList) -> impl VNode<()> + use<> {
+        PrimitiveVnodeWrapper(self)
     }
 }
 
-impl VModel for PrimitiveVnodeWrapper<Rect> {
-    type Storage = Reactive<RectModel>;
-    type ParentProps = ();
-
-    fn get_parent_props(&self, _: crate::model::GetParentPropsFn<Self::ParentProps>) {
-        panic_when_call_unreachable()
-    }
+impl VModel<()> for PrimitiveVnodeWrapper<Image> {
+    type Storage = PrimitiveModel<ImageModel>;
 
     fn create(&self, ctx: &ModelCreateCtx) -> Self::Storage {
-        let init_style = read_or_default(&self.0.style, ImageStyle::default());
+        let model = Rc::new(RefCell::new(ImageModel {
+            el: Rc::new_cyclic(|weak| {
+                RefCell::new(RenderImage::new(InitRenderImage {
+                    this: weak.clone() as _,
+                    source: self.0.source.clone(),
+                    placeholder: self.0.placeholder.clone(),
+                    fallback: self.0.fallback.clone(),
+                    style: self.0.style.clone(),
+                    event_callback: self.0.on.clone(),
+                    ctx: &ctx.el_ctx,
+                }))
+            }),
+        }));
 
-        let init_state = RectModel {
-            el: Rc::new(RefCell::new(RenderRect::new(
-                init_style,
-                self.0.on.clone(),
-                &ctx.el_ctx,
-            ))),
-        };
+        let mut wl = WatcherList::new();
+        wl.watch_borrow_mut(
+            &model,
+            |this, _| this.el.borrow_mut().sources_updated(),
+            (
+                self.0.source.clone(),
+                self.0.placeholder.clone(),
+                self.0.fallback.clone(),
+            ),
+        )
+        .watch_borrow_mut(
+            &model,
+            |this, _| this.el.borrow_mut().style_updated(),
+            self.0.style.clone(),
+        );
 
-        Reactive::builder()
-            .dep(RectModel::update_style, self.0.style.clone())
-            .build(init_state)
+        PrimitiveModel {
+            _watcher_list: wl,
+            model,
+        }
     }
 
     fn update(&self, _: &mut Self::Storage, _: &ModelCreateCtx) {
@@ -55,24 +76,18 @@ impl VModel for PrimitiveVnodeWrapper<Rect> {
     }
 }
 
-pub struct RectModel {
-    el: Rc<RefCell<RenderRect>>,
-}
-
-impl RectModel {
-    fn update_style(&mut self, style: Option<&ImageStyle>) {
-        *self.el.borrow_mut().update_rect() = style.unwrap().clone();
-    }
+pub struct ImageModel {
+    el: Rc<RefCell<RenderImage>>,
 }
 
-impl EleModel for RectModel {
-    fn get_element(&self) -> Element {
-        self.el.clone()
+impl EleModel<()> for ImageModel {
+    fn get_element(&self) -> (Element, ()) {
+        (self.el.clone(), ())
     }
 }
 
-impl Model for RectModel {
-    fn visit(&self, f: &mut dyn FnMut(Element)) {
-        f(self.get_element())
+impl Model<()> for ImageModel {
+    fn visit(&self, f: &mut dyn FnMut(Element, ())) {
+        f(self.el.clone(), ())
     }
 }
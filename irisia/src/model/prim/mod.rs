@@ -2,21 +2,35 @@ use crate::{Handle, hook::watcher::WatcherList, prim_element::Element};
 
 use super::{EleModel, Model};
 
-pub use self::{block::Block, text::Text};
+pub use self::{block::Block, canvas::Canvas, image::Image, portal::Portal, svg::Svg, text::Text};
 pub use crate::prim_element::block::layout::DefaultLayouter;
 pub(crate) use block::SubmitChildren;
 
 mod block;
-// mod image;
+mod canvas;
+mod image;
+mod portal;
+mod svg;
 mod text;
 
 struct PrimitiveVnodeWrapper<T>(T);
 
+/// Storage of a primitive v-model, keeps the watchers that forward signal
+/// changes to the model alive.
 pub struct PrimitiveModel<T> {
     model: Handle<T>,
     _watcher_list: WatcherList,
 }
 
+impl<T> PrimitiveModel<T> {
+    pub fn new(model: Handle<T>, watcher_list: WatcherList) -> Self {
+        Self {
+            model,
+            _watcher_list: watcher_list,
+        }
+    }
+}
+
 impl<T, Cd> Model<Cd> for PrimitiveModel<T>
 where
     T: Model<Cd>,
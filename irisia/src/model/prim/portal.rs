@@ -0,0 +1,124 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    hook::{Signal, watcher::WatcherList},
+    model::{
+        EleModel, Model, ModelCreateCtx, VModel, VNode, component::Component,
+        control_flow::common_vmodel::DynVModel,
+    },
+    prim_element::{
+        EMCreateCtx, Element, RenderTree,
+        block::BlockStyle,
+        overlay::{OverlayPlacement, RenderPortal},
+    },
+};
+
+use super::{
+    Block, PrimitiveModel, PrimitiveVnodeWrapper, block::BlockModel, panic_when_call_unreachable,
+};
+
+/// Mounts its children into the overlay layer of the window, which is drawn
+/// above everything else and hit first, like popups, menus and dialogs. The
+/// children are placed against the layout box of the element the portal is
+/// in, while the portal itself takes no space.
+#[derive(Default)]
+pub struct Portal {
+    pub placement: Option<Signal<OverlayPlacement>>,
+    /// Hide the pointer from everything below the children, like dialogs, so
+    /// only the children and the layers above them are hit.
+    pub modal: Option<Signal<bool>>,
+    /// Style of the block holding the children in the overlay.
+    pub style: Option<Signal<BlockStyle>>,
+    pub children: Option<Signal<DynVModel<()>>>,
+}
+
+impl Component for Portal {
+    fn create(self, _watcher_list: &mut WatcherList) -> impl VNode<()> + use<> {
+        PrimitiveVnodeWrapper(self)
+    }
+}
+
+impl VModel<()> for PrimitiveVnodeWrapper<Portal> {
+    type Storage = PrimitiveModel<PortalModel>;
+
+    fn create(&self, ctx: &ModelCreateCtx) -> Self::Storage {
+        let el_ctx = &ctx.el_ctx;
+
+        // the content is a root of its own, laid out in the window
+        let content = PrimitiveVnodeWrapper(Block::<()> {
+            style: self.0.style.clone(),
+            children: self.0.children.clone(),
+            ..Default::default()
+        })
+        .create(&ModelCreateCtx::create_as_root(EMCreateCtx {
+            global_content: el_ctx.global_content.clone(),
+            parent: None,
+            paint_order_dirty: None,
+        }));
+
+        el_ctx.global_content.overlay().mount(
+            content.get_element().0,
+            el_ctx.parent.clone(),
+            self.0.placement.clone(),
+            self.0.modal.clone(),
+        );
+
+        let model = Rc::new(RefCell::new(PortalModel {
+            el: Rc::new_cyclic(|weak| RefCell::new(RenderPortal::new(weak.clone() as _, el_ctx))),
+            content,
+        }));
+
+        let mut wl = WatcherList::new();
+        wl.watch_borrow_mut(
+            &model,
+            |this, _| this.placement_updated(),
+            self.0.placement.clone(),
+        );
+
+        PrimitiveModel {
+            model,
+            _watcher_list: wl,
+        }
+    }
+
+    fn update(&self, _: &mut Self::Storage, _: &ModelCreateCtx) {
+        panic_when_call_unreachable()
+    }
+}
+
+pub struct PortalModel {
+    el: Rc<RefCell<RenderPortal>>,
+    content: PrimitiveModel<BlockModel<()>>,
+}
+
+impl PortalModel {
+    fn placement_updated(&self) {
+        // the location is updated before drawing
+        let content = self.content.get_element().0;
+        content.borrow().common().request_repaint();
+    }
+}
+
+impl Drop for PortalModel {
+    fn drop(&mut self) {
+        self.el
+            .borrow()
+            .common()
+            .ctx
+            .global_content
+            .overlay()
+            .unmount(&self.content.get_element().0);
+    }
+}
+
+impl Model<()> for PortalModel {
+    fn visit(&self, f: &mut dyn FnMut(Element, ())) {
+        f(self.el.clone(), ())
+    }
+}
+
+impl EleModel<()> for PortalModel {
+    fn get_element(&self) -> (Element, ()) {
+        (self.el.clone(), ())
+    }
+}
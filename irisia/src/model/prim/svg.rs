@@ -0,0 +1,83 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    hook::{
+        Signal,
+        watcher::{WatcherGuard, WatcherList},
+    },
+    model::{EleModel, Model, ModelCreateCtx, VModel, VNode, component::Component},
+    prim_element::{
+        Element, EventCallback,
+        svg::{RenderSvg, SvgSource, SvgStyle},
+    },
+};
+
+use super::{PrimitiveModel, PrimitiveVnodeWrapper, panic_when_call_unreachable};
+
+#[derive(Default)]
+pub struct Svg {
+    pub source: Option<Signal<SvgSource>>,
+    pub style: Option<Signal<SvgStyle>>,
+    pub on: Option<EventCallback>,
+}
+
+impl Component for Svg {
+    fn create(self, _watcher_list: &mut WatcherList) -> impl VNode<()> + use<> {
+        PrimitiveVnodeWrapper(self)
+    }
+}
+
+impl VModel<()> for PrimitiveVnodeWrapper<Svg> {
+    type Storage = PrimitiveModel<SvgModel>;
+
+    fn create(&self, ctx: &ModelCreateCtx) -> Self::Storage {
+        let model = Rc::new(RefCell::new(SvgModel {
+            el: Rc::new_cyclic(|weak| {
+                RefCell::new(RenderSvg::new(
+                    weak.clone() as _,
+                    self.0.source.clone(),
+                    self.0.style.clone(),
+                    self.0.on.clone(),
+                    &ctx.el_ctx,
+                ))
+            }),
+        }));
+
+        let mut wl = WatcherList::new();
+        wl.watch_borrow_mut(
+            &model,
+            |this, _| this.el.borrow_mut().source_updated(),
+            self.0.source.clone(),
+        )
+        .watch_borrow_mut(
+            &model,
+            |this, _| this.el.borrow_mut().style_updated(),
+            self.0.style.clone(),
+        );
+
+        PrimitiveModel {
+            _watcher_list: wl,
+            model,
+        }
+    }
+
+    fn update(&self, _: &mut Self::Storage, _: &ModelCreateCtx) {
+        panic_when_call_unreachable()
+    }
+}
+
+pub struct SvgModel {
+    el: Rc<RefCell<RenderSvg>>,
+}
+
+impl EleModel<()> for SvgModel {
+    fn get_element(&self) -> (Element, ()) {
+        (self.el.clone(), ())
+    }
+}
+
+impl Model<()> for SvgModel {
+    fn visit(&self, f: &mut dyn FnMut(Element, ())) {
+        f(self.el.clone(), ())
+    }
+}
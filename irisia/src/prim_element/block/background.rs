@@ -0,0 +1,301 @@
+use irisia_backend::skia_safe::{
+    Color, Color4f, ColorSpace, Image, Matrix, Paint, Point as SkPoint, Rect as SkRect, Shader,
+    TileMode, gradient_shader,
+};
+
+use crate::{
+    animation::{Interpolate, discrete},
+    prim_element::image::{ImageSampling, ObjectFit},
+    primitive::{Point, Size},
+};
+
+/// Fill of the padding box of a block.
+#[derive(Clone, Debug)]
+pub enum Background {
+    Color(Color),
+    /// `angle` is in degrees, clockwise from the top, like `linear-gradient` in css.
+    LinearGradient {
+        angle: f32,
+        stops: Vec<ColorStop>,
+    },
+    /// A circle reaching the farthest corner. `center` is relative to the box
+    /// size, `(0.5, 0.5)` is the middle.
+    RadialGradient {
+        center: Point<f32>,
+        stops: Vec<ColorStop>,
+    },
+    /// Sweep around `center` clockwise, starting from `angle` degrees clockwise
+    /// from the top.
+    ConicGradient {
+        center: Point<f32>,
+        angle: f32,
+        stops: Vec<ColorStop>,
+    },
+    /// Fitted into the padding box without repeating.
+    Image {
+        image: Image,
+        fit: ObjectFit,
+        sampling: ImageSampling,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ColorStop {
+    pub color: Color,
+    /// Position along the gradient, from 0 to 1.
+    pub offset: f32,
+}
+
+impl Background {
+    pub const TRANSPARENT: Self = Self::Color(Color::TRANSPARENT);
+
+    /// Returns `None` if nothing will be painted.
+    pub(super) fn to_paint(&self, rect: SkRect, dpi: f32) -> Option<Paint> {
+        let shader = match self {
+            Self::Color(color) => {
+                if *color == Color::TRANSPARENT {
+                    return None;
+                }
+                let mut paint = Paint::new(&Color4f::from(*color), &ColorSpace::new_srgb_linear());
+                paint.set_anti_alias(true);
+                return Some(paint);
+            }
+            Self::LinearGradient { angle, stops } => {
+                let (sin, cos) = angle.to_radians().sin_cos();
+                // the gradient line passes the center and its ends are on the
+                // perpendicular lines through the corners
+                let half_length = (rect.width() * sin.abs() + rect.height() * cos.abs()) / 2.0;
+                let half = SkPoint::new(sin * half_length, -cos * half_length);
+                let (colors, offsets) = split_stops(stops)?;
+                gradient_shader::linear(
+                    (rect.center() - half, rect.center() + half),
+                    colors.as_slice(),
+                    offsets.as_slice(),
+                    TileMode::Clamp,
+                    None,
+                    None,
+                )
+            }
+            Self::RadialGradient { center, stops } => {
+                let center = relative_point(rect, *center);
+                let radius = [
+                    (rect.left, rect.top),
+                    (rect.right, rect.top),
+                    (rect.left, rect.bottom),
+                    (rect.right, rect.bottom),
+                ]
+                .into_iter()
+                .map(|corner| (SkPoint::from(corner) - center).length())
+                .fold(0.0, f32::max);
+                let (colors, offsets) = split_stops(stops)?;
+                gradient_shader::radial(
+                    center,
+                    radius,
+                    colors.as_slice(),
+                    offsets.as_slice(),
+                    TileMode::Clamp,
+                    None,
+                    None,
+                )
+            }
+            Self::ConicGradient {
+                center,
+                angle,
+                stops,
+            } => {
+                let center = relative_point(rect, *center);
+                // skia sweeps from the right
+                let matrix = Matrix::rotate_deg_pivot(angle - 90.0, center);
+                let (colors, offsets) = split_stops(stops)?;
+                gradient_shader::sweep(
+                    center,
+                    colors.as_slice(),
+                    offsets.as_slice(),
+                    TileMode::Clamp,
+                    None,
+                    None,
+                    &matrix,
+                )
+            }
+            Self::Image {
+                image,
+                fit,
+                sampling,
+            } => image_shader(image, *fit, *sampling, rect, dpi),
+        }?;
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true).set_shader(shader);
+        Some(paint)
+    }
+}
+
+impl Default for Background {
+    fn default() -> Self {
+        Self::TRANSPARENT
+    }
+}
+
+impl From<Color> for Background {
+    fn from(value: Color) -> Self {
+        Self::Color(value)
+    }
+}
+
+impl PartialEq for Background {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Color(a), Self::Color(b)) => a == b,
+            (
+                Self::LinearGradient { angle, stops },
+                Self::LinearGradient {
+                    angle: angle2,
+                    stops: stops2,
+                },
+            ) => angle == angle2 && stops == stops2,
+            (
+                Self::RadialGradient { center, stops },
+                Self::RadialGradient {
+                    center: center2,
+                    stops: stops2,
+                },
+            ) => center == center2 && stops == stops2,
+            (
+                Self::ConicGradient {
+                    center,
+                    angle,
+                    stops,
+                },
+                Self::ConicGradient {
+                    center: center2,
+                    angle: angle2,
+                    stops: stops2,
+                },
+            ) => center == center2 && angle == angle2 && stops == stops2,
+            (
+                Self::Image {
+                    image,
+                    fit,
+                    sampling,
+                },
+                Self::Image {
+                    image: image2,
+                    fit: fit2,
+                    sampling: sampling2,
+                },
+            ) => image.unique_id() == image2.unique_id() && fit == fit2 && sampling == sampling2,
+            _ => false,
+        }
+    }
+}
+
+/// Colors and gradients of the same kind and stop count are interpolated,
+/// others are switched at the halfway.
+impl Interpolate for Background {
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        let stops = |from: &Vec<ColorStop>, to: &Vec<ColorStop>| {
+            (from.len() == to.len()).then(|| from.interpolate(to, t))
+        };
+
+        let value = match (self, to) {
+            (Self::Color(from), Self::Color(to)) => Some(Self::Color(from.interpolate(to, t))),
+            (
+                Self::LinearGradient { angle, stops: from },
+                Self::LinearGradient {
+                    angle: angle2,
+                    stops: to,
+                },
+            ) => stops(from, to).map(|stops| Self::LinearGradient {
+                angle: angle.interpolate(angle2, t),
+                stops,
+            }),
+            (
+                Self::RadialGradient {
+                    center,
+                    stops: from,
+                },
+                Self::RadialGradient {
+                    center: center2,
+                    stops: to,
+                },
+            ) => stops(from, to).map(|stops| Self::RadialGradient {
+                center: center.interpolate(center2, t),
+                stops,
+            }),
+            (
+                Self::ConicGradient {
+                    center,
+                    angle,
+                    stops: from,
+                },
+                Self::ConicGradient {
+                    center: center2,
+                    angle: angle2,
+                    stops: to,
+                },
+            ) => stops(from, to).map(|stops| Self::ConicGradient {
+                center: center.interpolate(center2, t),
+                angle: angle.interpolate(angle2, t),
+                stops,
+            }),
+            _ => None,
+        };
+
+        value.unwrap_or_else(|| discrete(self, to, t))
+    }
+}
+
+impl Interpolate for ColorStop {
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        Self {
+            color: self.color.interpolate(&to.color, t),
+            offset: self.offset.interpolate(&to.offset, t),
+        }
+    }
+}
+
+fn relative_point(rect: SkRect, point: Point<f32>) -> SkPoint {
+    SkPoint::new(
+        rect.left + rect.width() * point.x,
+        rect.top + rect.height() * point.y,
+    )
+}
+
+fn split_stops(stops: &[ColorStop]) -> Option<(Vec<Color>, Vec<f32>)> {
+    if stops.is_empty() {
+        return None;
+    }
+    Some(stops.iter().map(|stop| (stop.color, stop.offset)).unzip())
+}
+
+fn image_shader(
+    image: &Image,
+    fit: ObjectFit,
+    sampling: ImageSampling,
+    rect: SkRect,
+    dpi: f32,
+) -> Option<Shader> {
+    let intrinsic = Size {
+        width: image.width() as f32 * dpi,
+        height: image.height() as f32 * dpi,
+    };
+    let target = Size {
+        width: rect.width(),
+        height: rect.height(),
+    };
+    let size = fit.fitted_size(intrinsic, target);
+
+    let dst = SkRect::from_xywh(
+        rect.center_x() - size.width / 2.0,
+        rect.center_y() - size.height / 2.0,
+        size.width,
+        size.height,
+    );
+    let matrix = Matrix::rect_to_rect(SkRect::from_iwh(image.width(), image.height()), dst, None)?;
+
+    image.to_shader(
+        (TileMode::Decal, TileMode::Decal),
+        sampling.to_skia(),
+        &matrix,
+    )
+}
@@ -0,0 +1,177 @@
+use irisia_backend::skia_safe::{
+    Canvas, ClipOp, Color, Color4f, ColorSpace, Paint, PaintStyle, Path, PathEffect,
+    Point as SkPoint, RRect, Rect as SkRect, paint::Cap,
+};
+
+use crate::primitive::{corner::Corner, rect::Rect};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum BorderStyle {
+    #[default]
+    Solid,
+    Dashed,
+    Dotted,
+}
+
+pub(super) struct BorderProps {
+    pub width: Rect<f32>,
+    pub color: Rect<Color>,
+    pub style: Rect<BorderStyle>,
+    pub radius: Corner<f32>,
+    pub border_box: RRect,
+    pub padding_box: RRect,
+}
+
+pub(super) enum DrawBorder {
+    /// All sides are solid in one color, fill the ring at once.
+    Uniform(Paint),
+    Sides(Vec<DrawSide>),
+}
+
+pub(super) struct DrawSide {
+    /// The part of the ring belongs to this side, split at the diagonals of
+    /// the corners.
+    clip: Path,
+    paint: Paint,
+    /// Center line of the ring to stroke with dashes, `None` to fill the ring.
+    stroke: Option<RRect>,
+}
+
+impl DrawBorder {
+    pub fn new(props: BorderProps) -> Option<Self> {
+        let BorderProps {
+            width,
+            color,
+            style,
+            radius,
+            border_box,
+            padding_box,
+        } = props;
+
+        if width == Rect::all(0.0) {
+            return None;
+        }
+
+        let color_space = ColorSpace::new_srgb_linear();
+        let make_paint = |color: Color| {
+            let mut paint = Paint::new(&Color4f::from(color), &color_space);
+            paint.set_anti_alias(true);
+            paint
+        };
+
+        if color == Rect::all(color.left) && style == Rect::all(BorderStyle::Solid) {
+            return Some(Self::Uniform(make_paint(color.left)));
+        }
+
+        let outer = *border_box.rect();
+        let inner = *padding_box.rect();
+        let corner = |rect: SkRect| Corner {
+            left_top: SkPoint::new(rect.left, rect.top),
+            right_top: SkPoint::new(rect.right, rect.top),
+            left_bottom: SkPoint::new(rect.left, rect.bottom),
+            right_bottom: SkPoint::new(rect.right, rect.bottom),
+        };
+        let (o, i) = (corner(outer), corner(inner));
+
+        let sides = [
+            (
+                width.left,
+                color.left,
+                style.left,
+                [o.left_top, i.left_top, i.left_bottom, o.left_bottom],
+            ),
+            (
+                width.top,
+                color.top,
+                style.top,
+                [o.left_top, o.right_top, i.right_top, i.left_top],
+            ),
+            (
+                width.right,
+                color.right,
+                style.right,
+                [o.right_top, o.right_bottom, i.right_bottom, i.right_top],
+            ),
+            (
+                width.bottom,
+                color.bottom,
+                style.bottom,
+                [o.left_bottom, i.left_bottom, i.right_bottom, o.right_bottom],
+            ),
+        ];
+
+        let center_line = center_line(outer, width, radius);
+        let sides = sides
+            .into_iter()
+            .filter(|(width, color, ..)| *width > 0.0 && color.a() != 0)
+            .map(|(width, color, style, polygon)| {
+                let mut paint = make_paint(color);
+                let intervals = match style {
+                    BorderStyle::Solid => None,
+                    BorderStyle::Dashed => Some([width * 3.0, width * 2.0]),
+                    // zero-length dashes with round caps are dots
+                    BorderStyle::Dotted => {
+                        paint.set_stroke_cap(Cap::Round);
+                        Some([0.0, width * 2.0])
+                    }
+                };
+
+                let stroke = intervals.map(|intervals| {
+                    paint
+                        .set_style(PaintStyle::Stroke)
+                        .set_stroke_width(width)
+                        .set_path_effect(PathEffect::dash(&intervals, 0.0));
+                    center_line
+                });
+
+                DrawSide {
+                    clip: Path::polygon(&polygon, true, None, None),
+                    paint,
+                    stroke,
+                }
+            })
+            .collect();
+
+        Some(Self::Sides(sides))
+    }
+
+    pub fn draw(&self, canvas: &Canvas, border_box: &RRect, padding_box: &RRect) {
+        match self {
+            Self::Uniform(paint) => {
+                canvas.draw_drrect(border_box, padding_box, paint);
+            }
+            Self::Sides(sides) => {
+                for side in sides {
+                    canvas.save();
+                    canvas.clip_path(&side.clip, ClipOp::Intersect, true);
+                    match &side.stroke {
+                        Some(center_line) => canvas.draw_rrect(center_line, &side.paint),
+                        None => canvas.draw_drrect(border_box, padding_box, &side.paint),
+                    };
+                    canvas.restore();
+                }
+            }
+        }
+    }
+}
+
+fn center_line(outer: SkRect, width: Rect<f32>, radius: Corner<f32>) -> RRect {
+    let rect = SkRect {
+        left: outer.left + width.left / 2.0,
+        top: outer.top + width.top / 2.0,
+        right: outer.right - width.right / 2.0,
+        bottom: outer.bottom - width.bottom / 2.0,
+    };
+
+    let inset_radius = |r: f32, x_width: f32, y_width: f32| {
+        SkPoint::new((r - x_width / 2.0).max(0.0), (r - y_width / 2.0).max(0.0))
+    };
+    let radii = [
+        inset_radius(radius.left_top, width.left, width.top),
+        inset_radius(radius.right_top, width.right, width.top),
+        inset_radius(radius.right_bottom, width.right, width.bottom),
+        inset_radius(radius.left_bottom, width.left, width.bottom),
+    ];
+
+    RRect::new_rect_radii(rect, &radii)
+}
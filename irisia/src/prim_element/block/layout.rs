@@ -4,13 +4,9 @@ use crate::{
     prim_element::{
         RenderTreeExt,
         block::Child,
-        layout::{LayoutInput, SpaceConstraint},
-    },
-    primitive::{
-        Point,
-        length::{LengthStandard, LengthStandardGlobalPart},
-        size::Size,
+        layout::{Direction, FinalLayout, LayoutInput, SpaceConstraint},
     },
+    primitive::{Point, length::LengthStandard, size::Size},
 };
 
 pub trait BlockLayout<Cd>: Any {
@@ -23,7 +19,10 @@ pub trait BlockLayout<Cd>: Any {
 
 pub struct LayoutChild<'a, Cd> {
     child: &'a Child<Cd>,
-    length_standard: Size<LengthStandardGlobalPart>,
+    /// Standard of the content of the parent, which children inherit from.
+    length_standard: Size<LengthStandard>,
+    direction: Direction,
+    measuring: bool,
 }
 
 impl<'a, Cd> LayoutChild<'a, Cd> {
@@ -31,27 +30,56 @@ impl<'a, Cd> LayoutChild<'a, Cd> {
         &self.child.child_data
     }
 
+    /// Lay out the child, it's only measured if the parent is being measured.
     pub fn compute_layout(
         &self,
         constraint: Size<SpaceConstraint>,
         percentage_reference: Size<f32>,
+    ) -> Size<f32> {
+        if self.measuring {
+            return self.measure(constraint, percentage_reference);
+        }
+        self.child
+            .element
+            .borrow_mut()
+            .compute_layout_cached(self.layout_input(constraint, percentage_reference))
+    }
+
+    /// Measure the size of the child without placing its descendants, layouts
+    /// measuring a child under several constraints before laying it out should
+    /// call it for the measurements.
+    pub fn measure(
+        &self,
+        constraint: Size<SpaceConstraint>,
+        percentage_reference: Size<f32>,
     ) -> Size<f32> {
         self.child
             .element
             .borrow_mut()
-            .compute_layout_cached(LayoutInput {
-                constraint,
-                length_standard: self.length_standard.map_with(
-                    percentage_reference,
-                    |global, pr| LengthStandard {
-                        global,
-                        percentage_reference: pr,
-                    },
-                ),
-            })
+            .measure_cached(self.layout_input(constraint, percentage_reference))
     }
 
+    fn layout_input(
+        &self,
+        constraint: Size<SpaceConstraint>,
+        percentage_reference: Size<f32>,
+    ) -> LayoutInput {
+        LayoutInput {
+            constraint,
+            length_standard: self
+                .length_standard
+                .map_with(percentage_reference, |ls, pr| {
+                    ls.set_percentage_reference(pr)
+                }),
+            direction: self.direction,
+        }
+    }
+
+    /// Ignored if the parent is being measured.
     pub fn set_location(&self, location: Point<f32>) {
+        if self.measuring {
+            return;
+        }
         self.child
             .element
             .borrow_mut()
@@ -59,25 +87,47 @@ impl<'a, Cd> LayoutChild<'a, Cd> {
             .layout_output
             .location = location;
     }
+
+    /// Location set last time, and size computed by the last final layout.
+    /// Measurements don't change it.
+    pub fn layout(&self) -> FinalLayout {
+        self.child.element.borrow().common().layout_output
+    }
 }
 
+/// Children in the flow, absolutely positioned and fixed ones are laid out by
+/// the block itself.
 pub struct LayoutChildren<'a, Cd> {
-    children: &'a [Child<Cd>],
+    children: Vec<&'a Child<Cd>>,
     length_standard: &'a Size<LengthStandard>,
+    direction: Direction,
+    measuring: bool,
 }
 
 impl<'a, Cd> LayoutChildren<'a, Cd> {
-    pub(super) fn new(children: &'a [Child<Cd>], ls: &'a Size<LengthStandard>) -> Self {
+    pub(super) fn new(
+        children: &'a [Child<Cd>],
+        ls: &'a Size<LengthStandard>,
+        direction: Direction,
+        measuring: bool,
+    ) -> Self {
         Self {
-            children,
+            children: children
+                .iter()
+                .filter(|child| !child.element.borrow().common().position().is_out_of_flow())
+                .collect(),
             length_standard: ls,
+            direction,
+            measuring,
         }
     }
 
     pub fn iter(&self) -> impl Iterator<Item = LayoutChild<'_, Cd>> + use<'_, 'a, Cd> {
-        self.children.iter().map(|child| LayoutChild {
+        self.children.iter().map(|&child| LayoutChild {
             child,
-            length_standard: self.length_standard.as_ref().map(|ls| ls.global),
+            length_standard: *self.length_standard,
+            direction: self.direction,
+            measuring: self.measuring,
         })
     }
 
@@ -86,8 +136,11 @@ impl<'a, Cd> LayoutChildren<'a, Cd> {
             child: self
                 .children
                 .get(index)
+                .copied()
                 .expect("child id {index} is out of bounds"),
-            length_standard: self.length_standard.as_ref().map(|ls| ls.global),
+            length_standard: *self.length_standard,
+            direction: self.direction,
+            measuring: self.measuring,
         }
     }
 
@@ -106,11 +159,25 @@ impl<'a, Cd> LayoutChildren<'a, Cd> {
     pub fn length_standard(&self) -> &Size<LengthStandard> {
         &self.length_standard
     }
+
+    /// The resolved direction of the parent block, children inherit it.
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Whether the parent is only measured, the children are measured instead
+    /// of laid out and their locations are ignored then.
+    pub fn is_measuring(&self) -> bool {
+        self.measuring
+    }
 }
 
 impl<Cd> Drop for LayoutChildren<'_, Cd> {
     fn drop(&mut self) {
-        for child in self.children {
+        if self.measuring {
+            return;
+        }
+        for child in &self.children {
             child.element.borrow_mut().set_layout_completed();
         }
     }
@@ -138,18 +205,32 @@ impl<Cd> BlockLayout<Cd> for DefaultLayouter {
             }
         });
 
+        let mut child_widths = Vec::with_capacity(children.len());
         for child in children.iter() {
             let this_size = child.compute_layout(constraint, pct_ref);
+            child_widths.push(this_size.width);
             final_size.width = this_size.width.max(final_size.width);
             final_size.height = this_size.height.max(final_size.height);
         }
 
-        final_size.map_with(constraint, |len, cons| {
+        let final_size = final_size.map_with(constraint, |len, cons| {
             if let SpaceConstraint::Exact(exact) = cons {
                 exact
             } else {
                 len
             }
-        })
+        });
+
+        // children are stacked at the inline start, which is the right edge under rtl
+        if children.direction().is_rtl() {
+            for (child, width) in children.iter().zip(child_widths) {
+                child.set_location(Point {
+                    x: final_size.width - width,
+                    y: 0.0,
+                });
+            }
+        }
+
+        final_size
     }
 }
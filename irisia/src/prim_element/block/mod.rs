@@ -1,35 +1,98 @@
-use irisia_backend::skia_safe::Color;
+use std::{cell::Cell, rc::Rc};
+
+use irisia_backend::skia_safe::{BlendMode, ClipOp, Color, Path, RRect};
 use irisia_macros::style;
 use layout::{DefaultLayouter, LayoutChildren};
 use rect::{DrawRRect, DrawRRectProps};
+use shadow::ResolvedShadow;
 
 use crate as irisia;
 use crate::{
     hook::Signal,
-    primitive::{Length, Point, corner::Corner, line::Line, rect::Rect},
+    primitive::{
+        Length, Point, Transform,
+        corner::Corner,
+        length::{LengthStandard, MeasuredLength},
+        line::Line,
+        rect::Rect,
+    },
 };
 
 use super::{
-    Common, EMCreateCtx, Element, EmitEventArgs, EventCallback, RenderArgs, RenderTree,
-    RenderTreeExt, Size, WeakElement, layout::LayoutInput, read_or_default,
+    Common, EMCreateCtx, Element, EmitEventArgs, EventCallback, HitClip, RenderArgs, RenderTree,
+    RenderTreeExt, Size, WeakElement,
+    layout::{Direction, FinalLayout, LayoutInput, Position},
+    positioned::{OutOfFlow, layout_out_of_flow},
+    read_or_default,
     redraw_guard::RedrawGuard,
 };
 
+pub use background::{Background, ColorStop};
+pub use border::BorderStyle;
 pub use layout::BlockLayout;
+pub use shadow::BoxShadow;
 
+mod background;
+mod border;
 pub mod layout;
 mod rect;
+mod shadow;
 
-#[style(BlockStyleExt)]
-#[derive(Clone, Copy, PartialEq)]
+#[style(BlockStyleExt, interpolate)]
+#[derive(Clone, PartialEq)]
 pub struct BlockStyle {
     pub margin: Rect<Length>,
-    pub background: Color,
+    pub background: Background,
     pub border_width: Rect<Length>,
-    pub border_color: Color,
+    pub border_color: Rect<Color>,
+    #[style(discrete)]
+    pub border_style: Rect<BorderStyle>,
     pub border_radius: Corner<f32>,
     pub padding: Rect<Length>,
+    #[style(discrete)]
     pub box_sizing: BoxSizing,
+    /// Inline direction of this block and its descendants, `None` to inherit
+    /// from the parent. `left` and `right` of `margin`, `padding` and the border
+    /// are the inline start and end sides and swap under [`Direction::Rtl`].
+    #[style(discrete)]
+    pub direction: Option<Direction>,
+    /// Font size of this block and its descendants, which `em` refers to.
+    /// `em` and percentages of it refer to the inherited one, auto inherits.
+    pub font_size: Length,
+    /// Painted from the last to the first, outer shadows don't take space in layout.
+    pub box_shadow: Vec<BoxShadow>,
+    /// Opacity of the block and its descendants as a whole, they are neither
+    /// painted nor hit by the pointer at zero.
+    pub opacity: f32,
+    /// How the block and its descendants are blended with what's behind.
+    #[style(discrete)]
+    pub blend_mode: BlendMode,
+    /// Applied when drawing and hit-testing, the layout is not affected.
+    pub transform: Transform,
+    /// Percentages refer to the border box size, the center by default.
+    pub transform_origin: Point<Length>,
+    /// Clip the descendants when drawing and hit-testing.
+    #[style(discrete)]
+    pub clip: Clip,
+    /// Positioned blocks are painted above the static siblings, and absolutely
+    /// positioned descendants are placed in their padding boxes.
+    #[style(discrete)]
+    pub position: Position,
+    /// Distance to the sides of the containing block, or the offset if
+    /// positioned relatively. `left` and `right` swap under [`Direction::Rtl`].
+    pub inset: Rect<Length>,
+    /// Order of painting and hit-testing among the siblings.
+    #[style(discrete)]
+    pub z_index: i32,
+}
+
+/// Where the descendants of a block are visible, rounded by `border_radius`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Clip {
+    #[default]
+    None,
+    PaddingBox,
+    ContentBox,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Default)]
@@ -42,12 +105,27 @@ pub enum BoxSizing {
 impl BlockStyle {
     pub const DEFAULT: Self = Self {
         margin: Rect::all(Length::Auto),
-        background: Color::TRANSPARENT,
+        background: Background::TRANSPARENT,
         border_width: Rect::all(Length::Auto),
-        border_color: Color::BLACK,
+        border_color: Rect::all(Color::BLACK),
+        border_style: Rect::all(BorderStyle::Solid),
         border_radius: Corner::all(0.0),
         padding: Rect::all(Length::Auto),
         box_sizing: BoxSizing::ContentBox,
+        direction: None,
+        font_size: Length::Auto,
+        box_shadow: Vec::new(),
+        opacity: 1.0,
+        blend_mode: BlendMode::SrcOver,
+        transform: Transform::IDENTITY,
+        transform_origin: Point::all(Length::Measured(MeasuredLength {
+            percent: 0.5,
+            ..MeasuredLength::ZERO
+        })),
+        clip: Clip::None,
+        position: Position::Static,
+        inset: Rect::all(Length::Auto),
+        z_index: 0,
     };
 }
 
@@ -66,6 +144,13 @@ pub struct RenderBlock<Cd> {
     layouter: Option<Signal<dyn BlockLayout<Cd>>>,
     style: Option<Signal<BlockStyle>>,
     cached_background_rect: Option<DrawRRect>,
+    clip_rrect: Option<RRect>,
+    content_origin: Point<f32>,
+    padding_box_origin: Point<f32>,
+    is_containing_block: bool,
+    /// Indices of the children from the bottom to the top.
+    paint_order: Vec<usize>,
+    paint_order_dirty: Rc<Cell<bool>>,
     children: ElementList<Cd>,
     // prev_content_length_standard: Option<Size<LengthStandard>>,
     common: Common,
@@ -76,19 +161,29 @@ pub struct InitRenderBlock<'a, Cd = ()> {
     pub layouter: Option<Signal<dyn BlockLayout<Cd>>>,
     pub style: Option<Signal<BlockStyle>>,
     pub children: ElementList<Cd>,
+    /// The flag given to the children in [`EMCreateCtx`].
+    pub paint_order_dirty: Rc<Cell<bool>>,
     pub event_callback: Option<EventCallback>,
     pub ctx: &'a EMCreateCtx,
 }
 
 impl<Cd> RenderBlock<Cd> {
     pub fn new(init: InitRenderBlock<Cd>) -> Self {
-        Self {
+        let mut this = Self {
             layouter: init.layouter,
             style: init.style,
             cached_background_rect: None,
+            clip_rrect: None,
+            content_origin: Point::all(0.0),
+            padding_box_origin: Point::all(0.0),
+            is_containing_block: false,
+            paint_order: Vec::new(),
+            paint_order_dirty: init.paint_order_dirty,
             children: init.children,
             common: Common::new(init.this, init.event_callback, init.ctx),
-        }
+        };
+        this.update_positioning();
+        this
     }
 
     pub fn layouter_updated(&mut self) {
@@ -101,36 +196,68 @@ impl<Cd> RenderBlock<Cd> {
         self.cached_background_rect.take();
         self.common.request_reflow();
         self.common.request_repaint();
+        self.update_positioning();
     }
 
     pub fn update_children(&mut self) -> RedrawGuard<ElementList<Cd>> {
         self.common.request_reflow();
         self.children.0.clear();
+        self.paint_order_dirty.set(true);
         RedrawGuard::new(&mut self.children, &mut self.common)
     }
-}
 
-impl<Cd: 'static> RenderTree for RenderBlock<Cd> {
-    fn render(&mut self, args: RenderArgs, draw_location: Point<f32>) {
-        if let Some(rect) = &self.cached_background_rect {
-            rect.draw(args.canvas, draw_location);
-        } else {
-            panic!("cannot render before layout")
-        }
+    fn update_positioning(&mut self) {
+        let default_style = BlockStyle::DEFAULT;
+        let style = read_or_default(&self.style, &default_style);
+        self.common.set_position(style.position, style.inset);
+        self.common.set_z_index(style.z_index);
+    }
 
-        for child in self.children.0.iter_mut() {
-            child.element.borrow_mut().render_entry(args, draw_location);
+    fn update_paint_order(&mut self) {
+        if !self.paint_order_dirty.replace(false) {
+            return;
         }
+
+        let children = &self.children.0;
+        self.paint_order.clear();
+        self.paint_order.extend(0..children.len());
+        // stable, so the tree order is kept among equal keys
+        self.paint_order
+            .sort_by_key(|&index| children[index].element.borrow().common().paint_order_key());
     }
+}
 
-    fn compute_layout(
+impl<Cd: 'static> RenderBlock<Cd> {
+    /// Only the size is computed if `measuring`, nothing is stored then.
+    fn layout(
         &mut self,
         LayoutInput {
             constraint,
             length_standard,
+            direction,
         }: LayoutInput,
+        measuring: bool,
     ) -> Size<f32> {
-        let style = read_or_default(&self.style, &BlockStyle::DEFAULT);
+        let default_style = BlockStyle::DEFAULT;
+        let style = read_or_default(&self.style, &default_style);
+        let direction = style.direction.unwrap_or(direction);
+
+        let inherited_font_size = length_standard.width.font_size;
+        let font_size = length_standard
+            .width
+            .set_percentage_reference(inherited_font_size)
+            .resolve(style.font_size)
+            .unwrap_or(inherited_font_size);
+        let length_standard = length_standard.map(|ls| LengthStandard { font_size, ..ls });
+
+        // the root contains absolutely positioned blocks without a positioned ancestor
+        let is_containing_block = style.position.is_positioned()
+            || !style.transform.is_identity()
+            || self.common.ctx.parent.is_none();
+        let global_content = self.common.ctx.global_content.clone();
+        if is_containing_block && !measuring {
+            global_content.containing_blocks().push_frame();
+        }
 
         let resolve_rect = |rect: Rect<Length>| {
             rect.map_with(
@@ -139,11 +266,12 @@ impl<Cd: 'static> RenderTree for RenderBlock<Cd> {
             )
         };
 
-        let margin = resolve_rect(style.margin);
-        let border = resolve_rect(style.border_width);
-        let padding = resolve_rect(style.padding);
+        let margin = resolve_rect(direction.mirror_rect(style.margin));
+        let border = resolve_rect(direction.mirror_rect(style.border_width));
+        let padding = resolve_rect(direction.mirror_rect(style.padding));
+        let content_offset = margin + border + padding;
 
-        let white_space_size = (margin + border + padding)
+        let white_space_size = content_offset
             .split_into_hv_components()
             .map(|Line { start, end }| start + end)
             .to_size();
@@ -164,33 +292,238 @@ impl<Cd: 'static> RenderTree for RenderBlock<Cd> {
                 })
             });
 
-        self.children
-            .0
-            .iter()
-            .for_each(|e| e.element.borrow_mut().clear_layout_cache());
+        // children left out by the layouter are hidden
+        if !measuring {
+            for child in &self.children.0 {
+                child.element.borrow_mut().common_mut().layout_output = FinalLayout::HIDDEN;
+            }
+        }
 
         let layouter = read_or_default(&self.layouter, &DefaultLayouter);
         let content_size = layouter.compute_layout(
-            LayoutChildren::new(&mut self.children.0, &content_length_standard),
+            LayoutChildren::new(
+                &mut self.children.0,
+                &content_length_standard,
+                direction,
+                measuring,
+            ),
             content_constraint,
         );
 
         let outer_size = content_size + white_space_size;
-        self.cached_background_rect = Some(DrawRRect::new(DrawRRectProps {
-            background: style.background,
-            border_color: style.border_color,
+        if measuring {
+            return outer_size;
+        }
+
+        self.is_containing_block = is_containing_block;
+        self.content_origin = Point {
+            x: content_offset.left,
+            y: content_offset.top,
+        };
+
+        let viewport = length_standard
+            .width
+            .global
+            .viewport_size
+            .map(|len| len as f32);
+        // the absolutely positioned descendants passed to the containing block
+        let mut passed_out_of_flow = Vec::new();
+        for child in &self.children.0 {
+            let position = {
+                let child = child.element.borrow();
+                passed_out_of_flow.extend_from_slice(&child.common().out_of_flow);
+                child.common().position()
+            };
+            let out_of_flow = || OutOfFlow {
+                element: child.element.clone(),
+                direction,
+                inherited: content_length_standard.width,
+            };
+            match position {
+                Position::Absolute => {
+                    passed_out_of_flow.push(out_of_flow());
+                    let Some(out_of_flow) = global_content.containing_blocks().defer(out_of_flow())
+                    else {
+                        continue;
+                    };
+                    let containing_block = out_of_flow
+                        .element
+                        .borrow()
+                        .common()
+                        .containing_block
+                        .unwrap_or(viewport);
+                    layout_out_of_flow(&out_of_flow, containing_block);
+                }
+                Position::Fixed => layout_out_of_flow(&out_of_flow(), viewport),
+                Position::Static | Position::Relative => {}
+            }
+        }
+
+        let shadows: Vec<ResolvedShadow> = style
+            .box_shadow
+            .iter()
+            .map(|shadow| {
+                let resolve = |len, std: &LengthStandard| std.resolve(len).unwrap_or(0.0);
+                ResolvedShadow {
+                    offset: Point {
+                        x: resolve(shadow.offset.x, &length_standard.width),
+                        y: resolve(shadow.offset.y, &length_standard.height),
+                    },
+                    blur: resolve(shadow.blur, &length_standard.width),
+                    spread: resolve(shadow.spread, &length_standard.width),
+                    color: shadow.color,
+                    inset: shadow.inset,
+                }
+            })
+            .collect();
+
+        // shadows are drawn around the border box, which is inside the margin
+        let ink_overflow = shadows
+            .iter()
+            .fold(Rect::all(0.0f32), |acc, shadow| {
+                acc.map_with(shadow.extent(), f32::max)
+            })
+            .map_with(margin, |extent, margin| (extent - margin).max(0.0));
+        self.common.set_ink_overflow(ink_overflow);
+        self.common.set_compositing(style.opacity, style.blend_mode);
+
+        let padding_box_offset = margin + border;
+        self.padding_box_origin = Point {
+            x: padding_box_offset.left,
+            y: padding_box_offset.top,
+        };
+        if self.is_containing_block {
+            let padding_box_size = outer_size
+                - padding_box_offset
+                    .split_into_hv_components()
+                    .map(|Line { start, end }| start + end)
+                    .to_size();
+            for out_of_flow in global_content.containing_blocks().pop_frame() {
+                layout_out_of_flow(&out_of_flow, padding_box_size);
+            }
+            passed_out_of_flow.clear();
+        }
+        self.common.out_of_flow = passed_out_of_flow;
+
+        let transform = (!style.transform.is_identity()).then(|| {
+            // relative to the border box
+            let border_box_size = outer_size
+                - margin
+                    .split_into_hv_components()
+                    .map(|Line { start, end }| start + end)
+                    .to_size();
+            let own_length_standard = length_standard
+                .map_with(border_box_size, |ls, size| {
+                    ls.set_percentage_reference(size)
+                })
+                .to_point();
+            let origin = style
+                .transform_origin
+                .map_with(own_length_standard, |len, ls| {
+                    ls.resolve(len).unwrap_or(0.0)
+                });
+
+            style.transform.to_matrix(
+                Point {
+                    x: margin.left + origin.x,
+                    y: margin.top + origin.y,
+                },
+                own_length_standard,
+            )
+        });
+        self.common.set_transform(transform);
+
+        let background_rect = DrawRRect::new(DrawRRectProps {
+            background: style.background.clone(),
+            border_color: direction.mirror_rect(style.border_color),
+            border_style: direction.mirror_rect(style.border_style),
             border_radius: style.border_radius,
             margin,
             border,
             outer_size,
-        }));
+            shadows,
+            dpi: length_standard.width.global.dpi,
+        });
+        self.clip_rrect = background_rect.clip_rrect(style.clip, padding, style.border_radius);
+        self.cached_background_rect = Some(background_rect);
 
         outer_size
     }
+}
+
+impl<Cd: 'static> RenderTree for RenderBlock<Cd> {
+    fn render(&mut self, args: RenderArgs, draw_location: Point<f32>) {
+        if let Some(rect) = &self.cached_background_rect {
+            rect.draw(args.canvas, draw_location);
+        } else {
+            panic!("cannot render before layout")
+        }
+
+        if let Some(clip) = &self.clip_rrect {
+            args.canvas.save();
+            args.canvas.clip_rrect(
+                clip.with_offset((draw_location.x, draw_location.y)),
+                ClipOp::Intersect,
+                true,
+            );
+        }
+
+        let mut args = args;
+        if self.is_containing_block {
+            args.containing_block = draw_location + self.padding_box_origin;
+        }
+
+        self.update_paint_order();
+        let content_location = draw_location + self.content_origin;
+        for &index in &self.paint_order {
+            let mut child = self.children.0[index].element.borrow_mut();
+            child.render_entry(args, content_location);
+        }
+
+        if self.clip_rrect.is_some() {
+            args.canvas.restore();
+        }
+    }
+
+    fn compute_layout(&mut self, inputs: LayoutInput) -> Size<f32> {
+        self.layout(inputs, false)
+    }
+
+    fn measure(&mut self, inputs: LayoutInput) -> Size<f32> {
+        self.layout(inputs, true)
+    }
 
     fn children_emit_event(&mut self, args: &mut EmitEventArgs) {
-        for child in &self.children.0 {
-            child.element.borrow_mut().emit_event(args);
+        let hit_clip = match self.clip_rrect {
+            Some(clip) => {
+                // not drawn yet or squashed by the transform, nothing visible
+                let (Some(location), Some(inverse)) = (
+                    self.common.canvas_location(),
+                    self.common.device_matrix().and_then(|m| m.invert()),
+                ) else {
+                    return;
+                };
+
+                Some(HitClip {
+                    inverse,
+                    path: Path::rrect(clip.with_offset((location.x, location.y)), None),
+                })
+            }
+            None => None,
+        };
+
+        let clipped = hit_clip.is_some();
+        args.clips.extend(hit_clip);
+
+        self.update_paint_order();
+
+        // the topmost is hit first
+        for &index in self.paint_order.iter().rev() {
+            self.children.0[index].element.borrow_mut().emit_event(args);
+        }
+
+        if clipped {
+            args.clips.pop();
         }
     }
 
@@ -1,24 +1,33 @@
-use irisia_backend::skia_safe::{
-    Canvas, ClipOp, Color, Color4f, ColorSpace, Paint, Point as SkPoint, RRect, Rect as SkRect,
-};
+use irisia_backend::skia_safe::{Canvas, Color, Paint, Point as SkPoint, RRect, Rect as SkRect};
 
 use crate::primitive::{Point, corner::Corner, rect::Rect, size::Size};
 
+use super::{
+    Clip,
+    background::Background,
+    border::{BorderProps, BorderStyle, DrawBorder},
+    shadow::{DrawShadow, ResolvedShadow},
+};
+
 pub(super) struct DrawRRect {
+    shadows: Vec<DrawShadow>,
     fill_content: Option<Paint>,
-    fill_border_content: Option<Paint>,
+    border: Option<DrawBorder>,
     border_content_rrect: RRect,
     content_rrect: RRect,
 }
 
 #[derive(Debug)]
 pub(super) struct DrawRRectProps {
-    pub background: Color,
-    pub border_color: Color,
+    pub background: Background,
+    pub border_color: Rect<Color>,
+    pub border_style: Rect<BorderStyle>,
     pub border_radius: Corner<f32>,
     pub margin: Rect<f32>,
     pub border: Rect<f32>,
     pub outer_size: Size<f32>,
+    pub shadows: Vec<ResolvedShadow>,
+    pub dpi: f32,
 }
 
 impl DrawRRect {
@@ -26,10 +35,13 @@ impl DrawRRect {
         let DrawRRectProps {
             background,
             border_color,
+            border_style,
             border_radius,
             margin,
             border,
             outer_size,
+            shadows,
+            dpi,
         } = props;
 
         let border_content_rect = SkRect {
@@ -58,29 +70,58 @@ impl DrawRRect {
             RRect::new_rect_radii(rect, &arr)
         };
 
-        let color_space = ColorSpace::new_srgb_linear();
+        let fill_content = background.to_paint(content_rect, dpi);
 
-        let fill_content = if background != Color::TRANSPARENT {
-            let mut paint = Paint::new(&Color4f::from(background), &color_space);
-            paint.set_anti_alias(true);
-            Some(paint)
-        } else {
-            None
-        };
+        let border_content_rrect = make_rrect(border_content_rect);
+        let content_rrect = make_rrect(content_rect);
 
-        let fill_border_content = if border != Rect::all(0.0) {
-            let mut paint = Paint::new(&Color4f::from(border_color), &color_space);
-            paint.set_anti_alias(true);
-            Some(paint)
-        } else {
-            None
-        };
+        let border = DrawBorder::new(BorderProps {
+            width: border,
+            color: border_color,
+            style: border_style,
+            radius: border_radius,
+            border_box: border_content_rrect,
+            padding_box: content_rrect,
+        });
 
         Self {
+            // the first shadow is on the top
+            shadows: shadows
+                .iter()
+                .rev()
+                .map(|shadow| DrawShadow::new(shadow, border_content_rrect, content_rrect))
+                .collect(),
             fill_content,
-            fill_border_content,
-            border_content_rrect: make_rrect(border_content_rect),
-            content_rrect: make_rrect(content_rect),
+            border,
+            border_content_rrect,
+            content_rrect,
+        }
+    }
+
+    /// Shape to clip the children with, relative to the left-top of the block.
+    pub fn clip_rrect(&self, clip: Clip, padding: Rect<f32>, radius: Corner<f32>) -> Option<RRect> {
+        match clip {
+            Clip::None => None,
+            Clip::PaddingBox => Some(self.content_rrect),
+            Clip::ContentBox => {
+                let padding_box = self.content_rrect.rect();
+                let rect = SkRect {
+                    left: padding_box.left + padding.left,
+                    top: padding_box.top + padding.top,
+                    right: padding_box.right - padding.right,
+                    bottom: padding_box.bottom - padding.bottom,
+                };
+
+                let inset_radius =
+                    |r: f32, x: f32, y: f32| SkPoint::new((r - x).max(0.0), (r - y).max(0.0));
+                let radii = [
+                    inset_radius(radius.left_top, padding.left, padding.top),
+                    inset_radius(radius.right_top, padding.right, padding.top),
+                    inset_radius(radius.right_bottom, padding.right, padding.bottom),
+                    inset_radius(radius.left_bottom, padding.left, padding.bottom),
+                ];
+                Some(RRect::new_rect_radii(rect, &radii))
+            }
         }
     }
 
@@ -89,13 +130,20 @@ impl DrawRRect {
         canvas.save();
         canvas.translate(location);
 
+        for shadow in self.shadows.iter().filter(|s| !s.is_inset()) {
+            shadow.draw(canvas);
+        }
+
         if let Some(fill_content) = &self.fill_content {
             canvas.draw_rrect(self.content_rrect, fill_content);
         }
 
-        if let Some(fill_border_content) = &self.fill_border_content {
-            canvas.clip_rrect(self.content_rrect, ClipOp::Difference, true);
-            canvas.draw_rrect(self.border_content_rrect, fill_border_content);
+        for shadow in self.shadows.iter().filter(|s| s.is_inset()) {
+            shadow.draw(canvas);
+        }
+
+        if let Some(border) = &self.border {
+            border.draw(canvas, &self.border_content_rrect, &self.content_rrect);
         }
 
         canvas.restore();
@@ -0,0 +1,171 @@
+use irisia_backend::skia_safe::{
+    BlurStyle, Canvas, ClipOp, Color, Color4f, ColorSpace, MaskFilter, Paint, RRect,
+};
+
+use crate::{
+    animation::{Interpolate, discrete},
+    primitive::{Length, Point, rect::Rect},
+};
+
+/// A shadow of the border box, like `box-shadow` in css.
+#[derive(Clone, Copy, PartialEq)]
+pub struct BoxShadow {
+    pub offset: Point<Length>,
+    /// Blur radius, the edge fades out over twice of it.
+    pub blur: Length,
+    /// Grow the shadow before blurring, negative values shrink it.
+    pub spread: Length,
+    pub color: Color,
+    /// Cast the shadow inside the padding box instead of outside the border box.
+    pub inset: bool,
+}
+
+impl BoxShadow {
+    pub const DEFAULT: Self = Self {
+        offset: Point::all(Length::ZERO),
+        blur: Length::ZERO,
+        spread: Length::ZERO,
+        color: Color::BLACK,
+        inset: false,
+    };
+}
+
+impl Default for BoxShadow {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl Interpolate for BoxShadow {
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        Self {
+            offset: self.offset.interpolate(&to.offset, t),
+            blur: self.blur.interpolate(&to.blur, t),
+            spread: self.spread.interpolate(&to.spread, t),
+            color: self.color.interpolate(&to.color, t),
+            inset: discrete(&self.inset, &to.inset, t),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct ResolvedShadow {
+    pub offset: Point<f32>,
+    pub blur: f32,
+    pub spread: f32,
+    pub color: Color,
+    pub inset: bool,
+}
+
+impl ResolvedShadow {
+    /// How far the shadow reaches out of the border box on each side.
+    pub fn extent(&self) -> Rect<f32> {
+        if self.inset {
+            return Rect::all(0.0);
+        }
+
+        let reach = self.spread + blur_reach(self.blur);
+        Rect {
+            left: reach - self.offset.x,
+            top: reach - self.offset.y,
+            right: reach + self.offset.x,
+            bottom: reach + self.offset.y,
+        }
+        .map(|x| x.max(0.0))
+    }
+}
+
+// the blur radius is twice the standard deviation like in css, and the
+// gaussian is invisible after three standard deviations.
+fn blur_sigma(blur: f32) -> f32 {
+    blur / 2.0
+}
+
+fn blur_reach(blur: f32) -> f32 {
+    blur_sigma(blur) * 3.0
+}
+
+pub(super) struct DrawShadow {
+    paint: Paint,
+    shape: Shape,
+}
+
+enum Shape {
+    Outer {
+        border_box: RRect,
+        shadow: RRect,
+    },
+    Inset {
+        padding_box: RRect,
+        outer: RRect,
+        hole: RRect,
+    },
+}
+
+impl DrawShadow {
+    pub fn new(shadow: &ResolvedShadow, border_box: RRect, padding_box: RRect) -> Self {
+        let mut paint = Paint::new(&Color4f::from(shadow.color), &ColorSpace::new_srgb_linear());
+        paint.set_anti_alias(true);
+        if shadow.blur > 0.0 {
+            paint.set_mask_filter(MaskFilter::blur(
+                BlurStyle::Normal,
+                blur_sigma(shadow.blur),
+                false,
+            ));
+        }
+
+        let offset = (shadow.offset.x, shadow.offset.y);
+        let shape = if shadow.inset {
+            let hole = padding_box
+                .with_inset((shadow.spread, shadow.spread))
+                .with_offset(offset);
+
+            // large enough to cover the padding box after blurring
+            let reach = blur_reach(shadow.blur) + shadow.spread.abs();
+            let outer = RRect::new_rect(
+                padding_box
+                    .rect()
+                    .with_outset((reach + shadow.offset.x.abs(), reach + shadow.offset.y.abs())),
+            );
+
+            Shape::Inset {
+                padding_box,
+                outer,
+                hole,
+            }
+        } else {
+            Shape::Outer {
+                border_box,
+                shadow: border_box
+                    .with_outset((shadow.spread, shadow.spread))
+                    .with_offset(offset),
+            }
+        };
+
+        Self { paint, shape }
+    }
+
+    pub fn is_inset(&self) -> bool {
+        matches!(self.shape, Shape::Inset { .. })
+    }
+
+    pub fn draw(&self, canvas: &Canvas) {
+        canvas.save();
+        match &self.shape {
+            Shape::Outer { border_box, shadow } => {
+                // outer shadows are never visible under the box
+                canvas.clip_rrect(border_box, ClipOp::Difference, true);
+                canvas.draw_rrect(shadow, &self.paint);
+            }
+            Shape::Inset {
+                padding_box,
+                outer,
+                hole,
+            } => {
+                canvas.clip_rrect(padding_box, ClipOp::Intersect, true);
+                canvas.draw_drrect(outer, hole, &self.paint);
+            }
+        }
+        canvas.restore();
+    }
+}
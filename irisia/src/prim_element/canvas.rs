@@ -0,0 +1,117 @@
+use irisia_backend::skia_safe::{Canvas as SkCanvas, ClipOp, Rect as SkRect};
+
+use super::{
+    Common, EMCreateCtx, EventCallback, RenderArgs, RenderTree, Size, WeakElement,
+    layout::{LayoutInput, SpaceConstraint},
+};
+use crate::{
+    hook::{Listener, Signal, listener::StrongListener, track, utils::CallbackAction},
+    primitive::{Length, Point},
+};
+
+/// Paints the content with the size of the element. The canvas is translated
+/// to the element location and clipped to its box.
+pub type PaintFn = dyn Fn(&SkCanvas, Size<f32>);
+
+pub struct RenderCanvas {
+    paint: Option<Signal<PaintFn>>,
+    preferred_size: Option<Signal<Size<Length>>>,
+    /// Depends on the signals read by `paint` last time, replaced on every
+    /// repaint so signals no longer read are dropped.
+    paint_deps: Option<StrongListener>,
+    common: Common,
+}
+
+impl RenderCanvas {
+    pub fn new(
+        this: WeakElement,
+        paint: Option<Signal<PaintFn>>,
+        preferred_size: Option<Signal<Size<Length>>>,
+        event_callback: Option<EventCallback>,
+        ctx: &EMCreateCtx,
+    ) -> Self {
+        Self {
+            paint,
+            preferred_size,
+            paint_deps: None,
+            common: Common::new(this, event_callback, ctx),
+        }
+    }
+
+    pub fn paint_updated(&mut self) {
+        self.common.request_repaint();
+    }
+
+    fn repaint_listener(&self) -> StrongListener {
+        let element = self.common.element().clone();
+        let global_content = self.common.ctx.global_content.clone();
+        Listener::new(|_| {
+            move |action: CallbackAction| {
+                if action.is_update() {
+                    global_content.request_repaint(&element);
+                }
+                true
+            }
+        })
+    }
+
+    pub fn preferred_size_updated(&mut self) {
+        self.common.request_reflow();
+        self.common.request_repaint();
+    }
+}
+
+impl RenderTree for RenderCanvas {
+    fn render(&mut self, args: RenderArgs, draw_location: Point<f32>) {
+        let Some(paint) = &self.paint else {
+            return;
+        };
+
+        let size = self.common.layout_output.size;
+        let canvas = args.canvas;
+        let save_count = canvas.save();
+        canvas.translate((draw_location.x, draw_location.y));
+        canvas.clip_rect(
+            SkRect::from_wh(size.width, size.height),
+            ClipOp::Intersect,
+            true,
+        );
+        let paint = paint.read();
+        let listener = self.repaint_listener();
+        track(listener.downgrade(), || paint(canvas, size));
+        self.paint_deps = Some(listener);
+        canvas.restore_to_count(save_count);
+    }
+
+    fn compute_layout(
+        &mut self,
+        LayoutInput {
+            constraint,
+            length_standard,
+            direction: _,
+        }: LayoutInput,
+    ) -> Size<f32> {
+        let preferred = match &self.preferred_size {
+            Some(size) => *size.read(),
+            None => Size::all(Length::Auto),
+        };
+
+        preferred
+            .map_with(length_standard, |len, ls| ls.resolve(len).unwrap_or(0.0))
+            .map_with(constraint, |len, cons| match cons {
+                SpaceConstraint::Exact(exact) => exact,
+                SpaceConstraint::Available(available) => len.min(available),
+                SpaceConstraint::MinContent | SpaceConstraint::MaxContent => len,
+            })
+    }
+
+    fn children_emit_event(&mut self, _: &mut super::EmitEventArgs) {}
+
+    fn common_mut(&mut self) -> &mut Common {
+        &mut self.common
+    }
+
+    fn common(&self) -> &Common {
+        &self.common
+    }
+}
@@ -1,20 +1,51 @@
+use irisia_backend::skia_safe::{BlendMode, Matrix, Paint};
+
 use super::{
-    EMCreateCtx, EmitEventArgs, EventCallback, RenderTree, WeakElement,
-    layout::{FinalLayout, LayoutInput},
+    EMCreateCtx, EmitEventArgs, EventCallback, RenderArgs, RenderTree, WeakElement,
+    layout::{Direction, FinalLayout, LayoutCache, LayoutInput, Position},
+    positioned::OutOfFlow,
+};
+use crate::{
+    WeakHandle,
+    primitive::{Length, Point, Rect, Size, length::LengthStandard},
 };
-use crate::{WeakHandle, primitive::Rect};
 
+/// State every element has, like its layout result and event callback.
 pub struct Common {
     prev_cursor_over: bool,
     element: WeakElement,
-    pub ctx: EMCreateCtx,
-    pub prev_draw_region: Option<Rect<f32>>,
-    pub event_callback: Option<EventCallback>,
-    pub layout_input: Option<LayoutInput>,
-    pub layout_output: FinalLayout,
+    pub(crate) ctx: EMCreateCtx,
+    pub(crate) prev_draw_region: Option<Rect<f32>>,
+    pub(crate) event_callback: Option<EventCallback>,
+    pub(crate) layout_cache: LayoutCache,
+    pub(crate) layout_output: FinalLayout,
+    ink_overflow: Rect<f32>,
+    opacity: f32,
+    blend_mode: BlendMode,
+    transform: Option<Matrix>,
+    placement: Option<Placement>,
+    position: Position,
+    inset: Rect<Length>,
+    z_index: i32,
+    relative_offset: Point<f32>,
+    /// Padding box size of the containing block used last time if absolutely
+    /// positioned, it's reused when only a part of the tree reflows.
+    pub(crate) containing_block: Option<Size<f32>>,
+    /// Absolutely positioned descendants whose containing block is an ancestor,
+    /// registered to it again when the layout is reused from the cache.
+    pub(crate) out_of_flow: Vec<OutOfFlow>,
+}
+
+/// Where the element was drawn last time.
+#[derive(Clone, Copy)]
+struct Placement {
+    /// Canvas matrix before applying the transform of the element.
+    parent_matrix: Matrix,
+    parent_location: Point<f32>,
 }
 
 impl Common {
+    /// `el` is the element owning this, usually got from `Rc::new_cyclic`.
     pub fn new(
         el: WeakHandle<dyn RenderTree>,
         event_callback: Option<EventCallback>,
@@ -26,31 +57,273 @@ impl Common {
             element: el,
             event_callback,
             layout_output: FinalLayout::HIDDEN,
-            layout_input: None,
+            layout_cache: LayoutCache::EMPTY,
+            ink_overflow: Rect::all(0.0),
+            opacity: 1.0,
+            blend_mode: BlendMode::SrcOver,
+            transform: None,
+            placement: None,
+            position: Position::Static,
+            inset: Rect::all(Length::Auto),
+            z_index: 0,
+            relative_offset: Point::all(0.0),
+            containing_block: None,
+            out_of_flow: Vec::new(),
             ctx: ctx.clone(),
         }
     }
 
+    /// The size and location computed by the last layout.
+    pub fn layout(&self) -> FinalLayout {
+        self.layout_output
+    }
+
+    /// Input of the last final layout, `None` if never laid out.
+    pub(crate) fn layout_input(&self) -> Option<LayoutInput> {
+        self.layout_cache.final_input()
+    }
+
+    /// Set how far the painting reaches out of the layout box on each side, like
+    /// shadows. Call it in [`RenderTree::compute_layout`] so the region is
+    /// repainted properly.
+    pub fn set_ink_overflow(&mut self, ink_overflow: Rect<f32>) {
+        self.ink_overflow = ink_overflow;
+    }
+
+    /// The layout box extended by the ink overflow, relative to the parent.
+    pub(crate) fn draw_region(&self) -> Rect<f32> {
+        let Rect {
+            left,
+            top,
+            right,
+            bottom,
+        } = self.layout_output.as_rect();
+        Rect {
+            left: left - self.ink_overflow.left,
+            top: top - self.ink_overflow.top,
+            right: right + self.ink_overflow.right,
+            bottom: bottom + self.ink_overflow.bottom,
+        }
+    }
+
+    /// Composite the element and its subtree as a whole. The subtree is neither
+    /// painted nor hit by the pointer if `opacity` is zero.
+    pub fn set_compositing(&mut self, opacity: f32, blend_mode: BlendMode) {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self.blend_mode = blend_mode;
+    }
+
+    pub(crate) fn is_invisible(&self) -> bool {
+        self.opacity == 0.0
+    }
+
+    /// Paint of the layer the subtree is drawn into, `None` to draw directly.
+    pub(crate) fn layer_paint(&self) -> Option<Paint> {
+        if self.opacity == 1.0 && self.blend_mode == BlendMode::SrcOver {
+            return None;
+        }
+
+        let mut paint = Paint::default();
+        paint
+            .set_alpha_f(self.opacity)
+            .set_blend_mode(self.blend_mode);
+        Some(paint)
+    }
+
+    /// Transform the element and its subtree when drawing and hit-testing.
+    /// The matrix is relative to the left-top of the element, it doesn't
+    /// affect the layout.
+    pub fn set_transform(&mut self, transform: Option<Matrix>) {
+        self.transform = transform;
+    }
+
+    /// Set how the element is positioned, `inset` is the distance to each side
+    /// of the containing block, or the offset if positioned relatively. The
+    /// parent reflows if it changes.
+    pub fn set_position(&mut self, position: Position, inset: Rect<Length>) {
+        if self.position == position && self.inset == inset {
+            return;
+        }
+        if self.position.is_positioned() != position.is_positioned() {
+            self.paint_order_changed();
+        }
+        self.position = position;
+        self.inset = inset;
+
+        // never laid out means the parent will lay it out anyway
+        if self.layout_input().is_some() {
+            self.request_reflow();
+            if let Some(parent) = &self.ctx.parent {
+                self.ctx.global_content.request_reflow(parent);
+            }
+        }
+    }
+
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    pub fn inset(&self) -> Rect<Length> {
+        self.inset
+    }
+
+    /// Siblings with greater z-index are painted later and hit first, those
+    /// equal are in the order of the tree with positioned ones above.
+    pub fn set_z_index(&mut self, z_index: i32) {
+        if self.z_index != z_index {
+            self.z_index = z_index;
+            self.paint_order_changed();
+            self.request_repaint();
+        }
+    }
+
+    pub fn z_index(&self) -> i32 {
+        self.z_index
+    }
+
+    pub(crate) fn paint_order_key(&self) -> (i32, bool) {
+        (self.z_index, self.position.is_positioned())
+    }
+
+    fn paint_order_changed(&self) {
+        if let Some(dirty) = &self.ctx.paint_order_dirty {
+            dirty.set(true);
+        }
+    }
+
+    /// Resolve the offset of relative positioning, called after the layout.
+    pub(crate) fn update_relative_offset(
+        &mut self,
+        length_standard: &Size<LengthStandard>,
+        direction: Direction,
+    ) {
+        if self.position != Position::Relative {
+            self.relative_offset = Point::all(0.0);
+            return;
+        }
+
+        let inset = direction.mirror_rect(self.inset).map_with(
+            length_standard.as_ref().to_point().split_hv_to_rect(),
+            |len, std| std.resolve(len),
+        );
+        self.relative_offset = Point {
+            x: inset.left.or(inset.right.map(|x| -x)).unwrap_or(0.0),
+            y: inset.top.or(inset.bottom.map(|x| -x)).unwrap_or(0.0),
+        };
+    }
+
+    /// Record where the element is drawn, called before rendering. Returns the
+    /// location its layout location is relative to, which depends on the
+    /// position. Fixed elements are drawn without the canvas matrix.
+    pub(crate) fn place(&mut self, args: &RenderArgs, parent_location: Point<f32>) -> Point<f32> {
+        let (parent_matrix, parent_location) = match self.position {
+            Position::Static => (args.canvas.local_to_device_as_3x3(), parent_location),
+            Position::Relative => (
+                args.canvas.local_to_device_as_3x3(),
+                parent_location + self.relative_offset,
+            ),
+            Position::Absolute => (args.canvas.local_to_device_as_3x3(), args.containing_block),
+            Position::Fixed => (Matrix::new_identity(), Point::ZERO),
+        };
+
+        self.placement = Some(Placement {
+            parent_matrix,
+            parent_location,
+        });
+        parent_location
+    }
+
+    /// The transform mapped to the canvas coordinates the element is drawn in.
+    pub(crate) fn canvas_transform(&self) -> Option<Matrix> {
+        let transform = self.transform.as_ref()?;
+        let location = self.canvas_location()?;
+
+        let mut matrix = Matrix::translate((location.x, location.y));
+        matrix
+            .pre_concat(transform)
+            .pre_translate((-location.x, -location.y));
+        Some(matrix)
+    }
+
+    /// Left-top of the element in the canvas coordinates it's drawn in.
+    pub(crate) fn canvas_location(&self) -> Option<Point<f32>> {
+        let placement = self.placement.as_ref()?;
+        Some(placement.parent_location + self.layout_output.location)
+    }
+
+    /// Matrix from the canvas coordinates the element is drawn in to the window.
+    pub(crate) fn device_matrix(&self) -> Option<Matrix> {
+        let placement = self.placement.as_ref()?;
+        Some(match self.canvas_transform() {
+            Some(transform) => Matrix::concat(&placement.parent_matrix, &transform),
+            None => placement.parent_matrix,
+        })
+    }
+
+    /// Bounds of the draw region in window coordinates, `None` if never drawn.
+    pub(crate) fn device_region(&self) -> Option<Rect<f32>> {
+        self.map_to_device(self.draw_region())
+    }
+
+    /// Bounds of the layout box in window coordinates, `None` if never drawn.
+    pub(crate) fn device_layout_box(&self) -> Option<Rect<f32>> {
+        self.map_to_device(self.layout_output.as_rect())
+    }
+
+    fn map_to_device(&self, rect: Rect<f32>) -> Option<Rect<f32>> {
+        let placement = self.placement.as_ref()?;
+        let region = rect + placement.parent_location.split_hv_to_rect();
+        let (mapped, _) = self.device_matrix()?.map_rect(region.to_skia_rect());
+        Some(Rect {
+            left: mapped.left,
+            top: mapped.top,
+            right: mapped.right,
+            bottom: mapped.bottom,
+        })
+    }
+
+    /// Emit pointer events of this element to its callback. Called by
+    /// [`RenderTreeExt::emit_event`](super::RenderTreeExt::emit_event) after the children.
     pub fn use_callback(&mut self, args: &mut EmitEventArgs) {
         // if self.layout_output.is_hidden() {
         //     return;
         // }
 
-        let draw_region = self.layout_output.as_rect().to_lagacy_region();
+        // map the pointer back through the transforms to test against the layout box
+        let inverse = self.device_matrix().and_then(|matrix| matrix.invert());
+        let layout_box = self.placement.as_ref().map(|placement| {
+            (self.layout_output.as_rect() + placement.parent_location.split_hv_to_rect())
+                .to_lagacy_region()
+        });
+        let clips = &args.clips;
+        let hit_test = |position: Point| match (&inverse, &layout_box) {
+            (Some(inverse), Some(layout_box)) => {
+                let local = inverse.map_point((position.x, position.y));
+                layout_box.contains_point(Point {
+                    x: local.x,
+                    y: local.y,
+                }) && clips.iter().all(|clip| clip.contains(position))
+            }
+            _ => false,
+        };
 
-        let events = args
-            .delta
-            .get_event(draw_region, &mut self.prev_cursor_over);
+        let events = args.delta.get_event(&hit_test, &mut self.prev_cursor_over);
 
         if let Some(sig) = &self.event_callback {
             args.queue.extend(events.map(|pe| (sig.clone(), pe)));
         }
     }
 
+    pub(crate) fn element(&self) -> &WeakElement {
+        &self.element
+    }
+
+    /// Redraw the element in next frame.
     pub fn request_repaint(&self) {
         self.ctx.global_content.request_repaint(&self.element);
     }
 
+    /// Compute the layout again in next frame, call it if the size may change.
     pub fn request_reflow(&self) {
         self.ctx.global_content.request_reflow(&self.element);
     }
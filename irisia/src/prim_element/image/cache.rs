@@ -0,0 +1,194 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    future::Future,
+    path::PathBuf,
+    pin::Pin,
+    rc::{Rc, Weak},
+};
+
+use anyhow::anyhow;
+use irisia_backend::skia_safe::{Data, Image, image::CachingHint};
+
+use crate::{Result, application::content::GlobalContent, prim_element::WeakElement};
+
+pub(crate) type BoxedBytesFuture = Pin<Box<dyn Future<Output = Result<Vec<u8>>>>>;
+
+/// Identifies a decoded image in the per-window [`ImageCache`].
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum ImageKey {
+    Path(PathBuf),
+    Named(Rc<str>),
+}
+
+#[derive(Clone)]
+pub enum LoadState {
+    Pending,
+    Ready(Image),
+    Failed,
+}
+
+pub(crate) enum LoadTask {
+    Encoded(Data),
+    Path(PathBuf),
+    Future(BoxedBytesFuture),
+}
+
+pub(crate) struct CacheEntry {
+    state: RefCell<LoadState>,
+    waiters: RefCell<Vec<WeakElement>>,
+}
+
+impl CacheEntry {
+    pub fn state(&self) -> LoadState {
+        self.state.borrow().clone()
+    }
+}
+
+/// Decoded images shared by all image elements of a window, so the same
+/// source is read and decoded only once.
+#[derive(Default)]
+pub struct ImageCache {
+    entries: RefCell<HashMap<ImageKey, Rc<CacheEntry>>>,
+}
+
+impl ImageCache {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the entry of `key`, or start loading it on the window's runtime.
+    /// `waiter` will be reflowed once the loading finished.
+    pub(crate) fn load(
+        &self,
+        key: &ImageKey,
+        task: impl FnOnce() -> LoadTask,
+        global_content: &Rc<GlobalContent>,
+        waiter: &WeakElement,
+    ) -> Rc<CacheEntry> {
+        let mut entries = self.entries.borrow_mut();
+        if let Some(entry) = entries.get(key) {
+            if let LoadState::Pending = *entry.state.borrow() {
+                entry.waiters.borrow_mut().push(waiter.clone());
+            }
+            return entry.clone();
+        }
+
+        let entry = Rc::new(CacheEntry {
+            state: RefCell::new(LoadState::Pending),
+            waiters: RefCell::new(vec![waiter.clone()]),
+        });
+        entries.insert(key.clone(), entry.clone());
+
+        tokio::task::spawn_local(run_task(
+            task(),
+            Some(key.clone()),
+            entry.clone(),
+            Rc::downgrade(global_content),
+        ));
+
+        entry
+    }
+
+    /// Decode `data` in background like the cached sources, but it's not cached
+    /// since it has no key. `waiter` will be reflowed once decoded.
+    pub(crate) fn decode(
+        &self,
+        data: Data,
+        global_content: &Rc<GlobalContent>,
+        waiter: &WeakElement,
+    ) -> Rc<CacheEntry> {
+        let entry = Rc::new(CacheEntry {
+            state: RefCell::new(LoadState::Pending),
+            waiters: RefCell::new(vec![waiter.clone()]),
+        });
+
+        tokio::task::spawn_local(run_task(
+            LoadTask::Encoded(data),
+            None,
+            entry.clone(),
+            Rc::downgrade(global_content),
+        ));
+
+        entry
+    }
+
+    /// Drop the decoded image of `key`, elements still using it are not affected.
+    pub fn evict(&self, key: &ImageKey) {
+        self.entries.borrow_mut().remove(key);
+    }
+
+    /// Drop a failed entry so the next load of `key` tries again, unless it has
+    /// been replaced already.
+    fn evict_failed(&self, key: &ImageKey, entry: &Rc<CacheEntry>) {
+        let mut entries = self.entries.borrow_mut();
+        if entries
+            .get(key)
+            .is_some_and(|cached| Rc::ptr_eq(cached, entry))
+        {
+            entries.remove(key);
+        }
+    }
+
+    pub fn clear(&self) {
+        self.entries.borrow_mut().clear();
+    }
+}
+
+async fn run_task(
+    task: LoadTask,
+    key: Option<ImageKey>,
+    entry: Rc<CacheEntry>,
+    global_content: Weak<GlobalContent>,
+) {
+    // reading files and decoding pixels are blocking, keep them off the window thread
+    let result = match task {
+        LoadTask::Encoded(data) => tokio::task::spawn_blocking(move || decode(data)).await,
+        LoadTask::Path(path) => {
+            tokio::task::spawn_blocking(move || decode(Data::new_copy(&std::fs::read(path)?))).await
+        }
+        LoadTask::Future(future) => match future.await {
+            Ok(bytes) => tokio::task::spawn_blocking(move || decode(Data::new_copy(&bytes))).await,
+            Err(err) => Ok(Err(err)),
+        },
+    };
+
+    let source = match &key {
+        Some(key) => format!("{key:?}"),
+        None => "of encoded bytes".to_owned(),
+    };
+    let state = match result {
+        Ok(Ok(image)) => LoadState::Ready(image),
+        Ok(Err(err)) => {
+            crate::error!("cannot load image {}: {}", source, err);
+            LoadState::Failed
+        }
+        Err(err) => {
+            crate::error!("image loading task of {} panicked: {}", source, err);
+            LoadState::Failed
+        }
+    };
+
+    let failed = matches!(state, LoadState::Failed);
+    *entry.state.borrow_mut() = state;
+    let waiters = entry.waiters.take();
+    let Some(global_content) = global_content.upgrade() else {
+        return;
+    };
+
+    // the elements keep the failed entry, new loads try again
+    if let (true, Some(key)) = (failed, &key) {
+        global_content.image_cache().evict_failed(key, &entry);
+    }
+
+    for waiter in &waiters {
+        global_content.request_reflow(waiter);
+        global_content.request_repaint(waiter);
+    }
+}
+
+fn decode(data: Data) -> Result<Image> {
+    Image::from_encoded(data)
+        .and_then(|image| image.make_raster_image(None, CachingHint::Allow))
+        .ok_or_else(|| anyhow!("unsupported or corrupted image data"))
+}
@@ -0,0 +1,377 @@
+use std::{future::Future, path::PathBuf, rc::Rc};
+
+use cache::{BoxedBytesFuture, CacheEntry, LoadTask};
+use irisia_backend::skia_safe::{
+    ClipOp, CubicResampler, Data, FilterMode, Image, MipmapMode, Paint, Point as SkPoint, RRect,
+    Rect as SkRect, SamplingOptions,
+};
+use irisia_macros::style;
+
+use super::{
+    Common, EMCreateCtx, EventCallback, RenderArgs, RenderTree, Size, WeakElement,
+    layout::{LayoutInput, fit_intrinsic_size},
+    read_or_default,
+};
+use crate as irisia;
+use crate::{
+    Result,
+    hook::Signal,
+    primitive::{Point, corner::Corner},
+};
+
+pub use cache::{ImageCache, ImageKey, LoadState};
+
+mod cache;
+
+/// Where the pixels of an image come from.
+#[derive(Clone)]
+pub enum ImageSource {
+    /// Encoded PNG, JPEG or WebP bytes, decoded in background. Not cached.
+    Encoded(Data),
+    /// An image that has already been decoded.
+    Decoded(Image),
+    /// A file read and decoded in background. Cached per window by its path.
+    Path(PathBuf),
+    /// Encoded bytes produced by a future on the window's runtime, decoded in
+    /// background. Cached per window by its key.
+    Future(ImageLoader),
+}
+
+#[derive(Clone)]
+pub struct ImageLoader {
+    key: Rc<str>,
+    load: Rc<dyn Fn() -> BoxedBytesFuture>,
+}
+
+impl ImageSource {
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        Self::Encoded(Data::new_copy(bytes))
+    }
+
+    pub fn from_path(path: impl Into<PathBuf>) -> Self {
+        Self::Path(path.into())
+    }
+
+    /// `load` is called only if no image of the same `key` has been cached.
+    pub fn from_future<F, Fut>(key: impl Into<Rc<str>>, load: F) -> Self
+    where
+        F: Fn() -> Fut + 'static,
+        Fut: Future<Output = Result<Vec<u8>>> + 'static,
+    {
+        Self::Future(ImageLoader {
+            key: key.into(),
+            load: Rc::new(move || Box::pin(load())),
+        })
+    }
+
+    fn id(&self) -> SourceId {
+        match self {
+            Self::Encoded(data) => SourceId::Encoded(data.as_bytes().as_ptr(), data.size()),
+            Self::Decoded(image) => SourceId::Decoded(image.unique_id()),
+            Self::Path(path) => SourceId::Key(ImageKey::Path(path.clone())),
+            Self::Future(loader) => SourceId::Key(ImageKey::Named(loader.key.clone())),
+        }
+    }
+}
+
+/// What a source is loaded from, an unchanged one is not loaded again.
+#[derive(PartialEq)]
+enum SourceId {
+    Encoded(*const u8, usize),
+    Decoded(u32),
+    Key(ImageKey),
+}
+
+impl From<Image> for ImageSource {
+    fn from(value: Image) -> Self {
+        Self::Decoded(value)
+    }
+}
+
+#[style(ImageStyleExt)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct ImageStyle {
+    pub object_fit: ObjectFit,
+    pub sampling: ImageSampling,
+    pub border_radius: Corner<f32>,
+}
+
+/// How the image is fitted into the box the layouter gives it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ObjectFit {
+    /// Scale to the box and keep the aspect ratio, the whole image is visible.
+    Contain,
+    /// Scale to the box and keep the aspect ratio, the overflowed part is clipped.
+    Cover,
+    /// Stretch to the box.
+    #[default]
+    Fill,
+    /// Keep the intrinsic size and center it in the box.
+    None,
+}
+
+impl ObjectFit {
+    /// Size of an image with `intrinsic` size after fitted into `target`.
+    pub(crate) fn fitted_size(self, intrinsic: Size<f32>, target: Size<f32>) -> Size<f32> {
+        match self {
+            Self::Fill => target,
+            Self::None => intrinsic,
+            Self::Contain | Self::Cover => {
+                let scale_x = target.width / intrinsic.width;
+                let scale_y = target.height / intrinsic.height;
+                let scale = if self == Self::Contain {
+                    scale_x.min(scale_y)
+                } else {
+                    scale_x.max(scale_y)
+                };
+                intrinsic.map(|x| x * scale)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ImageSampling {
+    Nearest,
+    #[default]
+    Linear,
+    Mipmap,
+    Cubic,
+}
+
+impl ImageSampling {
+    pub(crate) fn to_skia(self) -> SamplingOptions {
+        match self {
+            Self::Nearest => SamplingOptions::new(FilterMode::Nearest, MipmapMode::None),
+            Self::Linear => SamplingOptions::new(FilterMode::Linear, MipmapMode::None),
+            Self::Mipmap => SamplingOptions::new(FilterMode::Linear, MipmapMode::Linear),
+            Self::Cubic => CubicResampler::mitchell().into(),
+        }
+    }
+}
+
+impl ImageStyle {
+    pub const DEFAULT: Self = Self {
+        object_fit: ObjectFit::Fill,
+        sampling: ImageSampling::Linear,
+        border_radius: Corner::all(0.0),
+    };
+}
+
+impl Default for ImageStyle {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+enum LoadedImage {
+    Ready(Image),
+    Loading(Rc<CacheEntry>),
+}
+
+impl LoadedImage {
+    fn state(&self) -> LoadState {
+        match self {
+            Self::Ready(image) => LoadState::Ready(image.clone()),
+            Self::Loading(entry) => entry.state(),
+        }
+    }
+
+    fn ready_image(this: &Option<Self>) -> Option<Image> {
+        match this.as_ref()?.state() {
+            LoadState::Ready(image) => Some(image),
+            LoadState::Pending | LoadState::Failed => None,
+        }
+    }
+}
+
+pub struct InitRenderImage<'a> {
+    pub this: WeakElement,
+    pub source: Option<Signal<ImageSource>>,
+    pub placeholder: Option<Signal<ImageSource>>,
+    pub fallback: Option<Signal<ImageSource>>,
+    pub style: Option<Signal<ImageStyle>>,
+    pub event_callback: Option<EventCallback>,
+    pub ctx: &'a EMCreateCtx,
+}
+
+pub struct RenderImage {
+    sources: [Option<Signal<ImageSource>>; 3],
+    loaded: [Option<LoadedImage>; 3],
+    /// Sources `loaded` came from.
+    loaded_ids: [Option<SourceId>; 3],
+    style: Option<Signal<ImageStyle>>,
+    image: Option<Image>,
+    dpi: f32,
+    common: Common,
+}
+
+const SOURCE: usize = 0;
+const PLACEHOLDER: usize = 1;
+const FALLBACK: usize = 2;
+
+impl RenderImage {
+    pub fn new(init: InitRenderImage) -> Self {
+        let mut this = Self {
+            sources: [init.source, init.placeholder, init.fallback],
+            loaded: [None, None, None],
+            loaded_ids: [None, None, None],
+            style: init.style,
+            image: None,
+            dpi: 1.0,
+            common: Common::new(init.this, init.event_callback, init.ctx),
+        };
+        this.load_changed();
+        this
+    }
+
+    pub fn sources_updated(&mut self) {
+        if self.load_changed() {
+            self.common.request_reflow();
+            self.common.request_repaint();
+        }
+    }
+
+    /// Load the sources which are not loaded yet, returns whether any is.
+    fn load_changed(&mut self) -> bool {
+        let mut changed = false;
+        for index in [SOURCE, PLACEHOLDER, FALLBACK] {
+            let id = self.sources[index]
+                .as_ref()
+                .map(|source| source.read().id());
+            // a failed load is evicted from the cache, so try it again
+            let retry = matches!(id, Some(SourceId::Key(_)))
+                && matches!(
+                    self.loaded[index].as_ref().map(LoadedImage::state),
+                    Some(LoadState::Failed)
+                );
+            if id == self.loaded_ids[index] && !retry {
+                continue;
+            }
+
+            self.loaded[index] = self.load(&self.sources[index]);
+            self.loaded_ids[index] = id;
+            changed = true;
+        }
+        changed
+    }
+
+    pub fn style_updated(&mut self) {
+        self.common.request_repaint();
+    }
+
+    fn load(&self, source: &Option<Signal<ImageSource>>) -> Option<LoadedImage> {
+        let gc = &self.common.ctx.global_content;
+        let this = self.common.element();
+
+        Some(match &*source.as_ref()?.read() {
+            ImageSource::Encoded(data) => {
+                LoadedImage::Loading(gc.image_cache().decode(data.clone(), gc, this))
+            }
+            ImageSource::Decoded(image) => LoadedImage::Ready(image.clone()),
+            ImageSource::Path(path) => LoadedImage::Loading(gc.image_cache().load(
+                &ImageKey::Path(path.clone()),
+                || LoadTask::Path(path.clone()),
+                gc,
+                this,
+            )),
+            ImageSource::Future(loader) => LoadedImage::Loading(gc.image_cache().load(
+                &ImageKey::Named(loader.key.clone()),
+                || LoadTask::Future((loader.load)()),
+                gc,
+                this,
+            )),
+        })
+    }
+
+    /// The image to show: the source if ready, otherwise the placeholder while
+    /// pending, or the fallback if failed.
+    fn current_image(&self) -> Option<Image> {
+        match self.loaded[SOURCE].as_ref()?.state() {
+            LoadState::Ready(image) => Some(image),
+            LoadState::Pending => LoadedImage::ready_image(&self.loaded[PLACEHOLDER]),
+            LoadState::Failed => LoadedImage::ready_image(&self.loaded[FALLBACK]),
+        }
+    }
+
+    fn intrinsic_size(&self) -> Size<f32> {
+        match &self.image {
+            Some(image) => Size {
+                width: image.width() as f32 * self.dpi,
+                height: image.height() as f32 * self.dpi,
+            },
+            None => Size::all(0.0),
+        }
+    }
+}
+
+impl RenderTree for RenderImage {
+    fn render(&mut self, args: RenderArgs, draw_location: Point<f32>) {
+        let Some(image) = &self.image else {
+            return;
+        };
+
+        let style = read_or_default(&self.style, &ImageStyle::DEFAULT);
+        let size = self.common.layout_output.size;
+        let box_rect = SkRect::from_xywh(draw_location.x, draw_location.y, size.width, size.height);
+
+        let dst_size = style.object_fit.fitted_size(self.intrinsic_size(), size);
+
+        let dst_rect = SkRect::from_xywh(
+            box_rect.left + (size.width - dst_size.width) / 2.0,
+            box_rect.top + (size.height - dst_size.height) / 2.0,
+            dst_size.width,
+            dst_size.height,
+        );
+
+        let radii = [
+            style.border_radius.left_top,
+            style.border_radius.right_top,
+            style.border_radius.right_bottom,
+            style.border_radius.left_bottom,
+        ]
+        .map(|x| SkPoint::new(x, x));
+
+        let mut paint = Paint::default();
+        paint.set_anti_alias(true);
+
+        let canvas = args.canvas;
+        canvas.save();
+        canvas.clip_rrect(
+            RRect::new_rect_radii(box_rect, &radii),
+            ClipOp::Intersect,
+            true,
+        );
+        canvas.draw_image_rect_with_sampling_options(
+            image,
+            None,
+            dst_rect,
+            style.sampling.to_skia(),
+            &paint,
+        );
+        canvas.restore();
+    }
+
+    fn compute_layout(
+        &mut self,
+        LayoutInput {
+            constraint,
+            length_standard,
+            direction: _,
+        }: LayoutInput,
+    ) -> Size<f32> {
+        self.dpi = length_standard.width.global.dpi;
+        self.image = self.current_image();
+        fit_intrinsic_size(constraint, self.intrinsic_size())
+    }
+
+    fn children_emit_event(&mut self, _: &mut super::EmitEventArgs) {}
+
+    fn common_mut(&mut self) -> &mut Common {
+        &mut self.common
+    }
+
+    fn common(&self) -> &Common {
+        &self.common
+    }
+}
@@ -24,10 +24,137 @@ impl SpaceConstraint {
     }
 }
 
+/// Inline base direction of an element. Inherited from the parent unless
+/// overridden, the root of a window is always left-to-right.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Direction {
+    #[default]
+    Ltr,
+    Rtl,
+}
+
+impl Direction {
+    pub const fn is_rtl(self) -> bool {
+        matches!(self, Self::Rtl)
+    }
+
+    /// Map a rect whose `left`/`right` mean inline start/end to physical sides,
+    /// which swaps them under right-to-left.
+    pub fn mirror_rect<T>(self, rect: Rect<T>) -> Rect<T> {
+        match self {
+            Self::Ltr => rect,
+            Self::Rtl => Rect {
+                left: rect.right,
+                top: rect.top,
+                right: rect.left,
+                bottom: rect.bottom,
+            },
+        }
+    }
+}
+
+/// How an element is positioned, like `position` in css.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Position {
+    /// Laid out by the parent.
+    #[default]
+    Static,
+    /// Laid out by the parent, then moved by the inset when drawing.
+    Relative,
+    /// Out of the flow, placed by the inset in the padding box of the nearest
+    /// ancestor which is positioned or transformed, or the root.
+    Absolute,
+    /// Out of the flow, placed by the inset in the window.
+    Fixed,
+}
+
+impl Position {
+    pub const fn is_positioned(self) -> bool {
+        !matches!(self, Self::Static)
+    }
+
+    pub const fn is_out_of_flow(self) -> bool {
+        matches!(self, Self::Absolute | Self::Fixed)
+    }
+}
+
 #[derive(Clone, Copy, PartialEq)]
 pub struct LayoutInput {
     pub constraint: Size<SpaceConstraint>,
     pub length_standard: Size<LengthStandard>,
+    pub direction: Direction,
+}
+
+/// Measurements of an element, a slot for each kind of constraint.
+pub(crate) type Measurements = [Option<(LayoutInput, Size<f32>)>; 9];
+
+/// Results of recent layouts of an element. Containers measure a child under
+/// several constraints before its final layout, so like the cache of taffy a
+/// measurement is kept for each kind of constraint and they don't evict each
+/// other or the final layout.
+#[derive(Clone, Copy)]
+pub(crate) struct LayoutCache {
+    /// Input and size of the final layout, the children are placed for it.
+    final_layout: Option<(LayoutInput, Size<f32>)>,
+    measurements: Measurements,
+    /// The element changed since the final layout, which must run again even
+    /// under the same input.
+    dirty: bool,
+}
+
+impl LayoutCache {
+    pub const EMPTY: Self = Self {
+        final_layout: None,
+        measurements: [None; 9],
+        dirty: false,
+    };
+
+    /// Input of the last final layout, `None` if never laid out.
+    pub fn final_input(&self) -> Option<LayoutInput> {
+        self.final_layout.map(|(inputs, _)| inputs)
+    }
+
+    pub fn final_size(&self, inputs: &LayoutInput) -> Option<Size<f32>> {
+        match self.final_layout {
+            Some((cached, size)) if !self.dirty && cached == *inputs => Some(size),
+            _ => None,
+        }
+    }
+
+    pub fn set_final(&mut self, inputs: LayoutInput, size: Size<f32>) {
+        self.final_layout = Some((inputs, size));
+        self.dirty = false;
+    }
+
+    /// A measurement or the final layout under the same input.
+    pub fn measured_size(&self, inputs: &LayoutInput) -> Option<Size<f32>> {
+        match self.measurements[Self::slot(&inputs.constraint)] {
+            Some((cached, size)) if cached == *inputs => Some(size),
+            _ => self.final_size(inputs),
+        }
+    }
+
+    pub fn set_measured(&mut self, inputs: LayoutInput, size: Size<f32>) {
+        self.measurements[Self::slot(&inputs.constraint)] = Some((inputs, size));
+    }
+
+    /// Forget the measurements, and run the final layout again next time.
+    /// Returns the forgotten measurements.
+    pub fn invalidate(&mut self) -> Measurements {
+        self.dirty = true;
+        std::mem::replace(&mut self.measurements, [None; 9])
+    }
+
+    /// Exact and available space share a slot, then min-content and
+    /// max-content have one each, for both axes.
+    fn slot(constraint: &Size<SpaceConstraint>) -> usize {
+        let kind = |cons: SpaceConstraint| match cons {
+            SpaceConstraint::Exact(_) | SpaceConstraint::Available(_) => 0,
+            SpaceConstraint::MinContent => 1,
+            SpaceConstraint::MaxContent => 2,
+        };
+        kind(constraint.width) * 3 + kind(constraint.height)
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Debug)]
@@ -50,3 +177,103 @@ impl FinalLayout {
         self.size.is_empty()
     }
 }
+
+/// Size of a leaf with an intrinsic size, like an image. It's scaled to fit the
+/// constraint and keeps the aspect ratio unless both axes are exact.
+pub(crate) fn fit_intrinsic_size(
+    constraint: Size<SpaceConstraint>,
+    intrinsic: Size<f32>,
+) -> Size<f32> {
+    if intrinsic.is_empty() {
+        return constraint.map(|cons| match cons {
+            SpaceConstraint::Exact(exact) => exact,
+            _ => 0.0,
+        });
+    }
+
+    let ratio = intrinsic.width / intrinsic.height;
+    match (constraint.width, constraint.height) {
+        (SpaceConstraint::Exact(width), SpaceConstraint::Exact(height)) => Size { width, height },
+        (SpaceConstraint::Exact(width), height_cons) => Size {
+            width,
+            height: fit_available(width / ratio, height_cons),
+        },
+        (width_cons, SpaceConstraint::Exact(height)) => Size {
+            width: fit_available(height * ratio, width_cons),
+            height,
+        },
+        (width_cons, height_cons) => {
+            // shrink to the available space but keep the aspect ratio
+            let scale = [
+                (width_cons, intrinsic.width),
+                (height_cons, intrinsic.height),
+            ]
+            .into_iter()
+            .fold(1.0f32, |scale, (cons, len)| match cons {
+                SpaceConstraint::Available(available) if available < len => {
+                    scale.min(available / len)
+                }
+                _ => scale,
+            });
+            intrinsic.map(|x| x * scale)
+        }
+    }
+}
+
+fn fit_available(computed: f32, constraint: SpaceConstraint) -> f32 {
+    match constraint {
+        SpaceConstraint::Available(available) => computed.min(available),
+        _ => computed,
+    }
+}
+
+#[test]
+fn test_layout_cache() {
+    use crate::primitive::length::LengthStandardGlobalPart;
+    use SpaceConstraint::*;
+
+    let global = LengthStandardGlobalPart {
+        dpi: 1.0,
+        viewport_size: Size {
+            width: 100,
+            height: 100,
+        },
+        root_font_size: LengthStandardGlobalPart::DEFAULT_ROOT_FONT_SIZE,
+    };
+    let input = |width, height| LayoutInput {
+        constraint: Size { width, height },
+        length_standard: Size::all(global.root_standard(0.0)),
+        direction: Direction::Ltr,
+    };
+
+    let mut cache = LayoutCache::EMPTY;
+    let min = input(MinContent, MaxContent);
+    let max = input(MaxContent, MaxContent);
+    let exact = input(Exact(10.0), Exact(10.0));
+
+    cache.set_measured(min, Size::all(1.0));
+    cache.set_measured(max, Size::all(2.0));
+    cache.set_measured(exact, Size::all(3.0));
+    cache.set_final(input(Exact(50.0), Exact(50.0)), Size::all(50.0));
+    assert_eq!(cache.measured_size(&min), Some(Size::all(1.0)));
+    assert_eq!(cache.measured_size(&max), Some(Size::all(2.0)));
+    assert_eq!(cache.measured_size(&exact), Some(Size::all(3.0)));
+    // the final layout serves measurements too
+    assert_eq!(
+        cache.measured_size(&input(Exact(50.0), Exact(50.0))),
+        Some(Size::all(50.0))
+    );
+
+    // only the measurement of the same kind is evicted
+    let available = input(Available(20.0), Exact(10.0));
+    cache.set_measured(available, Size::all(4.0));
+    assert_eq!(cache.measured_size(&available), Some(Size::all(4.0)));
+    assert_eq!(cache.measured_size(&exact), None);
+    assert_eq!(cache.measured_size(&min), Some(Size::all(1.0)));
+
+    let forgotten = cache.invalidate();
+    assert!(forgotten.contains(&Some((min, Size::all(1.0)))));
+    assert_eq!(cache.measured_size(&min), None);
+    assert_eq!(cache.final_size(&input(Exact(50.0), Exact(50.0))), None);
+    assert!(cache.final_input().is_some());
+}
@@ -1,8 +1,10 @@
-use std::{ops::Deref, rc::Rc, time::Duration};
+use std::{cell::Cell, ops::Deref, rc::Rc, time::Duration};
 
 use callback_queue::CallbackQueue;
-use irisia_backend::skia_safe::{Canvas, ClipOp, Region as SkRegion};
-use layout::{FinalLayout, LayoutInput};
+use irisia_backend::skia_safe::{
+    Canvas, ClipOp, Matrix, Path, Region as SkRegion, canvas::SaveLayerRec,
+};
+use layout::{FinalLayout, LayoutCache, LayoutInput, Position};
 
 use crate::{
     Handle, WeakHandle,
@@ -14,49 +16,105 @@ use crate::{
     primitive::{Point, Rect, Region, size::Size},
 };
 
-pub(crate) use common::Common;
+pub use common::Common;
 pub mod block;
 pub(crate) mod callback_queue;
+pub mod canvas;
 mod common;
+pub mod image;
 pub mod layout;
-// pub mod image;
+pub mod overlay;
+pub(crate) mod positioned;
 mod redraw_guard;
+pub mod svg;
 pub mod text;
 
-pub(crate) type EventCallback = Signal<dyn Fn(PointerEvent)>;
+pub type EventCallback = Signal<dyn Fn(PointerEvent)>;
 
+/// Context of the window and the parent an element is created in. Obtain it
+/// from [`ModelCreateCtx::element_ctx`](crate::model::ModelCreateCtx::element_ctx)
+/// and pass it to [`Common::new`].
 #[derive(Clone)]
 pub struct EMCreateCtx {
     pub(crate) global_content: Rc<GlobalContent>,
     pub(crate) parent: Option<WeakElement>,
+    /// Shared by the children of a block, set when the z-index or the
+    /// positioning of one changes so the block sorts them again.
+    pub(crate) paint_order_dirty: Option<Rc<Cell<bool>>>,
 }
 
+/// A node of the render tree. Implement it to write a new primitive element,
+/// the state shared by all elements lives in a [`Common`] it owns.
+///
+/// Don't call the methods directly on children, use the wrappers of [`RenderTreeExt`]
+/// which take care of caching and bookkeeping.
 pub trait RenderTree: 'static {
+    /// Draw the element, `draw_location` is its left-top corner on the canvas.
+    /// Only called if the element intersects the dirty region.
     fn render(&mut self, args: RenderArgs, draw_location: Point<f32>);
+
+    /// Compute the size under the constraint. Containers lay out their children
+    /// here and set their locations relative to this element.
     fn compute_layout(&mut self, inputs: LayoutInput) -> Size<f32>;
+
+    /// Compute the size under the constraint without placing the children,
+    /// called when the parent measures this element before the final layout.
+    /// Containers measure their own children with [`RenderTreeExt::measure_cached`]
+    /// here, leaves can keep the default.
+    fn measure(&mut self, inputs: LayoutInput) -> Size<f32> {
+        self.compute_layout(inputs)
+    }
+
+    /// Forward the event to the children with [`RenderTreeExt::emit_event`].
+    /// Leaves do nothing.
     fn children_emit_event(&mut self, args: &mut EmitEventArgs);
+
     fn common_mut(&mut self) -> &mut Common;
     fn common(&self) -> &Common;
 }
 
 impl<T: RenderTree + ?Sized> RenderTreeExt for T {}
-pub(crate) trait RenderTreeExt: RenderTree {
+
+/// Provided methods of [`RenderTree`], containers call them on their children.
+pub trait RenderTreeExt: RenderTree {
+    /// Render the element if it's visible and intersects the dirty region.
+    /// The layout location of the element is relative to `parent_location`.
     fn render_entry(&mut self, args: RenderArgs, parent_location: Point<f32>) {
-        let final_layout = self.common_mut().layout_output;
-        self.common_mut().prev_draw_region = Some(final_layout.as_rect());
+        let final_layout = self.common().layout_output;
+        let common = self.common_mut();
+        let parent_location = common.place(&args, parent_location);
+        common.prev_draw_region = common.device_region();
 
-        if final_layout.is_hidden() {
+        if final_layout.is_hidden() || common.is_invisible() {
             return;
         }
 
-        let absolute_draw_region = final_layout.as_rect() + parent_location.split_hv_to_rect();
+        if !matches!(common.prev_draw_region, Some(region) if args.needs_redraw(region)) {
+            return;
+        }
 
-        if args.needs_redraw(absolute_draw_region) {
-            self.render(args, absolute_draw_region.get_location());
+        let save_count = args.canvas.save();
+        if common.position() == Position::Fixed {
+            args.canvas.reset_matrix();
         }
+        if let Some(transform) = common.canvas_transform() {
+            args.canvas.concat(&transform);
+        }
+        if let Some(paint) = common.layer_paint() {
+            args.canvas
+                .save_layer(&SaveLayerRec::default().paint(&paint));
+        }
+
+        self.render(args, parent_location + final_layout.location);
+        args.canvas.restore_to_count(save_count);
     }
 
+    /// Emit pointer events to the children and then this element. Invisible
+    /// subtrees are skipped.
     fn emit_event(&mut self, args: &mut EmitEventArgs) {
+        if self.common().is_invisible() {
+            return;
+        }
         self.children_emit_event(args);
         self.common_mut().use_callback(args);
     }
@@ -65,26 +123,39 @@ pub(crate) trait RenderTreeExt: RenderTree {
         self.common_mut().event_callback = Some(callback);
     }
 
+    /// Compute the layout, or reuse the last result if `inputs` is unchanged.
     fn compute_layout_cached(&mut self, inputs: LayoutInput) -> Size<f32> {
-        match self.common().layout_input {
-            Some(old_inputs) if old_inputs == inputs => self.common().layout_output.size,
-            _ => self.force_compute_layout_cached(inputs),
+        let common = self.common_mut();
+        match common.layout_cache.final_size(&inputs) {
+            Some(size) => {
+                common.layout_output.size = size;
+                common
+                    .ctx
+                    .global_content
+                    .containing_blocks()
+                    .defer_again(&common.out_of_flow);
+                size
+            }
+            None => self.force_compute_layout_cached(inputs),
         }
     }
 
     fn force_compute_layout_cached(&mut self, inputs: LayoutInput) -> Size<f32> {
         let computed_size = self.compute_layout(inputs);
         let common = self.common_mut();
-        common.layout_input = Some(inputs);
+        common.layout_cache.set_final(inputs, computed_size);
         common.layout_output.size = computed_size;
+        common.update_relative_offset(&inputs.length_standard, inputs.direction);
         computed_size
     }
 
+    /// Called once the location is set. Requests a repaint if the element moved.
     fn set_layout_completed(&mut self) {
         let common = self.common_mut();
         let new_layout = common.layout_output;
 
-        if matches!(common.prev_draw_region, Some(prev) if prev == new_layout.as_rect())
+        // both in window coordinates, under the parent location drawn last time
+        if matches!(common.prev_draw_region, Some(prev) if Some(prev) == common.device_region())
             || (common.layout_output.is_hidden() && new_layout.is_hidden())
         {
             return;
@@ -93,8 +164,19 @@ pub(crate) trait RenderTreeExt: RenderTree {
         common.request_repaint();
     }
 
+    /// Measure the size, or reuse a measurement or the final layout under the
+    /// same `inputs`. Neither the element nor its children are moved.
+    fn measure_cached(&mut self, inputs: LayoutInput) -> Size<f32> {
+        if let Some(size) = self.common().layout_cache.measured_size(&inputs) {
+            return size;
+        }
+        let size = self.measure(inputs);
+        self.common_mut().layout_cache.set_measured(inputs, size);
+        size
+    }
+
     fn clear_layout_cache(&mut self) {
-        self.common_mut().layout_input.take();
+        self.common_mut().layout_cache = LayoutCache::EMPTY;
         self.common_mut().layout_output = FinalLayout::HIDDEN;
     }
 }
@@ -104,9 +186,13 @@ pub struct RenderArgs<'a> {
     pub canvas: &'a Canvas,
     pub interval: Duration,
     pub dirty_region: Option<&'a SkRegion>,
+    /// Left-top of the padding box of the containing block on the canvas,
+    /// absolutely positioned elements are placed relative to it.
+    pub(crate) containing_block: Point<f32>,
 }
 
 impl RenderArgs<'_> {
+    /// Whether the region in window coordinates intersects the dirty region.
     pub fn needs_redraw(&self, draw_region: Rect<f32>) -> bool {
         let draw_rect = draw_region.round_to_skia_irect();
         if let Some(dirty_region) = self.dirty_region {
@@ -124,9 +210,27 @@ impl RenderArgs<'_> {
 pub type Element = Handle<dyn RenderTree>;
 pub type WeakElement = WeakHandle<dyn RenderTree>;
 
+/// Pointer state of an event dispatch, pass it unchanged to the children in
+/// [`RenderTree::children_emit_event`].
 pub struct EmitEventArgs<'a> {
     pub(crate) queue: &'a mut CallbackQueue,
     pub(crate) delta: PointerStateDelta,
+    /// Clips of the ancestors, the pointer outside any of them hits nothing.
+    pub(crate) clips: Vec<HitClip>,
+}
+
+/// A clip in the canvas coordinates of an element.
+pub(crate) struct HitClip {
+    /// From the window to the canvas coordinates.
+    pub inverse: Matrix,
+    pub path: Path,
+}
+
+impl HitClip {
+    pub fn contains(&self, position: Point) -> bool {
+        self.path
+            .contains(self.inverse.map_point((position.x, position.y)))
+    }
 }
 
 fn make_region(location: Point, width: f32, height: f32) -> Region {
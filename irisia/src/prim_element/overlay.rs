@@ -0,0 +1,354 @@
+use std::{cell::RefCell, rc::Rc};
+
+use super::{
+    Common, EMCreateCtx, Element, EmitEventArgs, RenderArgs, RenderTree, RenderTreeExt,
+    WeakElement,
+    layout::{Direction, LayoutInput, SpaceConstraint},
+};
+use crate::{
+    hook::Signal,
+    primitive::{Length, Point, Rect, Size, length::LengthStandardGlobalPart},
+};
+
+/// Where the content of a [`Portal`](crate::model::prim::Portal) is placed.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum OverlayPlacement {
+    /// Outside a side of the anchor and aligned with it along that side, `gap`
+    /// away from it. Moved to the opposite side if there's no room in the
+    /// window, and kept in the window along the side.
+    Anchored {
+        side: Side,
+        align: Align,
+        gap: Length,
+    },
+    /// Centered in the window regardless of the anchor, like dialogs.
+    Center,
+}
+
+impl OverlayPlacement {
+    pub const DEFAULT: Self = Self::Anchored {
+        side: Side::Bottom,
+        align: Align::Start,
+        gap: Length::ZERO,
+    };
+}
+
+impl Default for OverlayPlacement {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Side {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+/// Alignment along a side, start is the left or the top.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Align {
+    Start,
+    Center,
+    End,
+}
+
+/// Subtrees drawn above the root of the window, in the order they are mounted.
+pub(crate) struct OverlayLayer {
+    entries: RefCell<Vec<OverlayEntry>>,
+    /// Where the unmounted subtrees were drawn, to be cleared in next frame.
+    removed_regions: RefCell<Vec<Rect<f32>>>,
+}
+
+struct OverlayEntry {
+    content: Element,
+    /// The content is placed relative to the layout box of it, or the window
+    /// if there's none.
+    anchor: Option<WeakElement>,
+    placement: Option<Signal<OverlayPlacement>>,
+    modal: Option<Signal<bool>>,
+}
+
+impl OverlayLayer {
+    pub fn new() -> Self {
+        Self {
+            entries: RefCell::new(Vec::new()),
+            removed_regions: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn mount(
+        &self,
+        content: Element,
+        anchor: Option<WeakElement>,
+        placement: Option<Signal<OverlayPlacement>>,
+        modal: Option<Signal<bool>>,
+    ) {
+        content.borrow().common().request_repaint();
+        self.entries.borrow_mut().push(OverlayEntry {
+            content,
+            anchor,
+            placement,
+            modal,
+        });
+    }
+
+    pub fn unmount(&self, content: &Element) {
+        let mut entries = self.entries.borrow_mut();
+        let Some(index) = entries
+            .iter()
+            .position(|entry| Rc::ptr_eq(&entry.content, content))
+        else {
+            return;
+        };
+
+        let entry = entries.remove(index);
+        let mut content = entry.content.borrow_mut();
+        if let Some(region) = content.common_mut().prev_draw_region.take() {
+            self.removed_regions.borrow_mut().push(region);
+        }
+        content.common().request_repaint();
+    }
+
+    pub fn take_removed_regions(&self) -> Vec<Rect<f32>> {
+        std::mem::take(&mut self.removed_regions.borrow_mut())
+    }
+
+    /// Root elements of the subtrees, the topmost is the last.
+    pub fn elements(&self) -> Vec<Element> {
+        self.entries
+            .borrow()
+            .iter()
+            .map(|entry| entry.content.clone())
+            .collect()
+    }
+
+    /// Emit the event to the subtrees, the topmost first. Once one of them is
+    /// hit or a modal one is passed, the pointer is hidden from the layers
+    /// below, the root included, and they only see it leave.
+    pub fn emit_event(&self, args: &mut EmitEventArgs) {
+        let layers: Vec<(Element, bool)> = self
+            .entries
+            .borrow()
+            .iter()
+            .map(|entry| {
+                let modal = entry.modal.as_ref().is_some_and(|modal| *modal.read());
+                (entry.content.clone(), modal)
+            })
+            .collect();
+
+        for (content, modal) in layers.into_iter().rev() {
+            content.borrow_mut().emit_event(args);
+            if modal || !args.delta.cursor_may_over {
+                args.delta.blocked = true;
+            }
+        }
+    }
+
+    /// Lay out the subtrees in the window. Only those never laid out unless
+    /// `force`, the others reflow as usual.
+    pub fn layout(&self, global: LengthStandardGlobalPart, force: bool) {
+        let viewport = global.viewport_size.map(|x| x as f32);
+        let inputs = LayoutInput {
+            constraint: viewport.map(SpaceConstraint::Available),
+            length_standard: viewport.map(|x| global.root_standard(x)),
+            direction: Direction::Ltr,
+        };
+
+        for content in self.elements() {
+            let mut content = content.borrow_mut();
+            if force || content.common().layout_input().is_none() {
+                content.force_compute_layout_cached(inputs);
+            }
+        }
+    }
+
+    /// Move the subtrees to follow their anchors, where they were drawn last
+    /// time.
+    pub fn update_locations(&self, global: LengthStandardGlobalPart) {
+        for (content, location) in self.locations(global) {
+            let mut content = content.borrow_mut();
+            if content.common().layout_output.location != location {
+                content.common_mut().layout_output.location = location;
+                content.set_layout_completed();
+            }
+        }
+    }
+
+    /// Whether any subtree follows an element, which may be drawn elsewhere
+    /// whenever something is painted.
+    pub fn has_anchors(&self) -> bool {
+        self.entries
+            .borrow()
+            .iter()
+            .any(|entry| entry.anchor.is_some())
+    }
+
+    fn locations(&self, global: LengthStandardGlobalPart) -> Vec<(Element, Point<f32>)> {
+        let window = global.viewport_size.map(|x| x as f32);
+        let window_box = Rect::from_location_size(Point::ZERO, window);
+
+        self.entries
+            .borrow()
+            .iter()
+            .map(|entry| {
+                let anchor = entry
+                    .anchor
+                    .as_ref()
+                    .and_then(|anchor| anchor.upgrade())
+                    .and_then(|anchor| anchor.borrow().common().device_layout_box())
+                    .unwrap_or(window_box);
+                let placement = match &entry.placement {
+                    Some(placement) => *placement.read(),
+                    None => OverlayPlacement::DEFAULT,
+                };
+                let size = entry.content.borrow().common().layout_output.size;
+
+                let location = overlay_location(placement, anchor, size, global);
+                (entry.content.clone(), location)
+            })
+            .collect()
+    }
+}
+
+/// Left-top of the content of `size` in the window.
+fn overlay_location(
+    placement: OverlayPlacement,
+    anchor: Rect<f32>,
+    size: Size<f32>,
+    global: LengthStandardGlobalPart,
+) -> Point<f32> {
+    let window = global.viewport_size.map(|x| x as f32);
+
+    let (side, align, gap) = match placement {
+        OverlayPlacement::Anchored { side, align, gap } => (side, align, gap),
+        OverlayPlacement::Center => {
+            return Point {
+                x: (window.width - size.width) / 2.0,
+                y: (window.height - size.height) / 2.0,
+            };
+        }
+    };
+
+    let gap = global.root_standard(0.0).resolve(gap).unwrap_or(0.0);
+
+    match side {
+        Side::Top | Side::Bottom => Point {
+            x: aligned(anchor.left, anchor.right, size.width, window.width, align),
+            y: beside(
+                anchor.top - gap,
+                anchor.bottom + gap,
+                size.height,
+                window.height,
+                side == Side::Bottom,
+            ),
+        },
+        Side::Left | Side::Right => Point {
+            x: beside(
+                anchor.left - gap,
+                anchor.right + gap,
+                size.width,
+                window.width,
+                side == Side::Right,
+            ),
+            y: aligned(anchor.top, anchor.bottom, size.height, window.height, align),
+        },
+    }
+}
+
+/// Start of the content ending at `before` or starting at `after`.
+fn beside(before: f32, after: f32, len: f32, window: f32, prefer_after: bool) -> f32 {
+    let fits_after = after + len <= window;
+    let fits_before = before - len >= 0.0;
+    let use_after = if prefer_after {
+        fits_after || !fits_before
+    } else {
+        !fits_before && fits_after
+    };
+
+    if use_after { after } else { before - len }
+}
+
+fn aligned(start: f32, end: f32, len: f32, window: f32, align: Align) -> f32 {
+    let location = match align {
+        Align::Start => start,
+        Align::Center => (start + end - len) / 2.0,
+        Align::End => end - len,
+    };
+    location.min(window - len).max(0.0)
+}
+
+/// Where a portal is in the tree. It takes no space and draws nothing, its
+/// content lives in the overlay layer.
+pub(crate) struct RenderPortal {
+    common: Common,
+}
+
+impl RenderPortal {
+    pub fn new(this: WeakElement, ctx: &EMCreateCtx) -> Self {
+        Self {
+            common: Common::new(this, None, ctx),
+        }
+    }
+}
+
+impl RenderTree for RenderPortal {
+    fn render(&mut self, _: RenderArgs, _: Point<f32>) {}
+
+    fn compute_layout(&mut self, _: LayoutInput) -> Size<f32> {
+        Size::all(0.0)
+    }
+
+    fn children_emit_event(&mut self, _: &mut EmitEventArgs) {}
+
+    fn common_mut(&mut self) -> &mut Common {
+        &mut self.common
+    }
+
+    fn common(&self) -> &Common {
+        &self.common
+    }
+}
+
+#[test]
+fn test_overlay_location() {
+    let global = LengthStandardGlobalPart {
+        dpi: 1.0,
+        viewport_size: Size {
+            width: 200,
+            height: 100,
+        },
+        root_font_size: LengthStandardGlobalPart::DEFAULT_ROOT_FONT_SIZE,
+    };
+    let anchor = Rect {
+        left: 150.0,
+        top: 40.0,
+        right: 190.0,
+        bottom: 60.0,
+    };
+    let size = Size {
+        width: 80.0,
+        height: 30.0,
+    };
+    let location = |side, align| {
+        let placement = OverlayPlacement::Anchored {
+            side,
+            align,
+            gap: Length::ZERO,
+        };
+        let location = overlay_location(placement, anchor, size, global);
+        (location.x, location.y)
+    };
+
+    assert_eq!(location(Side::Bottom, Align::End), (110.0, 60.0));
+    // kept in the window
+    assert_eq!(location(Side::Bottom, Align::Start), (120.0, 60.0));
+    // no room on the right
+    assert_eq!(location(Side::Right, Align::Start), (70.0, 40.0));
+    assert_eq!(location(Side::Top, Align::Center), (120.0, 10.0));
+
+    let center = overlay_location(OverlayPlacement::Center, anchor, size, global);
+    assert_eq!((center.x, center.y), (60.0, 35.0));
+}
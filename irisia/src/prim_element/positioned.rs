@@ -0,0 +1,168 @@
+use std::{cell::RefCell, rc::Rc};
+
+use super::{
+    Element, RenderTreeExt,
+    layout::{Direction, LayoutInput, Position, SpaceConstraint},
+};
+use crate::primitive::{Point, Rect, Size, length::LengthStandard};
+
+/// Absolutely positioned elements waiting for the size of their containing
+/// blocks, one frame for each containing block being laid out.
+pub(crate) struct ContainingBlocks<T = OutOfFlow> {
+    frames: RefCell<Vec<Vec<T>>>,
+}
+
+/// An out-of-flow element with what it inherits from its parent.
+#[derive(Clone)]
+pub(crate) struct OutOfFlow {
+    pub element: Element,
+    pub direction: Direction,
+    /// Standard of the content of the parent, the percentage reference is
+    /// replaced by the size of the containing block.
+    pub inherited: LengthStandard,
+}
+
+impl<T> ContainingBlocks<T> {
+    pub fn new() -> Self {
+        Self {
+            frames: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Called by a containing block before laying out its children.
+    pub fn push_frame(&self) {
+        self.frames.borrow_mut().push(Vec::new());
+    }
+
+    /// Called by a containing block once its size is known, returns the
+    /// elements to lay out against it.
+    pub fn pop_frame(&self) -> Vec<T> {
+        self.frames
+            .borrow_mut()
+            .pop()
+            .expect("containing block frames are unbalanced")
+    }
+
+    /// Leave an absolutely positioned element to its containing block. Gives it
+    /// back if its containing block is not being laid out, which happens when
+    /// only a part of the tree reflows.
+    pub fn defer(&self, out_of_flow: T) -> Option<T> {
+        match self.frames.borrow_mut().last_mut() {
+            Some(frame) => {
+                frame.push(out_of_flow);
+                None
+            }
+            None => Some(out_of_flow),
+        }
+    }
+
+    /// Leave the absolutely positioned descendants of an element reused from
+    /// the cache to their containing block again, since they are not laid out
+    /// with it. They keep their layouts if the containing block is not being
+    /// laid out.
+    pub fn defer_again(&self, out_of_flow: &[T])
+    where
+        T: Clone,
+    {
+        if let Some(frame) = self.frames.borrow_mut().last_mut() {
+            frame.extend_from_slice(out_of_flow);
+        }
+    }
+}
+
+/// Whether both lists have the same elements, compared by identity.
+pub(crate) fn same_out_of_flow(a: &[OutOfFlow], b: &[OutOfFlow]) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b)
+            .all(|(a, b)| Rc::ptr_eq(&a.element, &b.element))
+}
+
+/// Lay out an absolutely positioned or fixed element against the padding box
+/// of its containing block, and place it relative to the box.
+pub(crate) fn layout_out_of_flow(
+    &OutOfFlow {
+        ref element,
+        direction,
+        inherited,
+    }: &OutOfFlow,
+    containing_block: Size<f32>,
+) {
+    let mut element = element.borrow_mut();
+    let length_standard = containing_block
+        .map(|percentage_reference| inherited.set_percentage_reference(percentage_reference));
+
+    let Rect {
+        left,
+        top,
+        right,
+        bottom,
+    } = direction.mirror_rect(element.common().inset()).map_with(
+        length_standard.as_ref().to_point().split_hv_to_rect(),
+        |len, std| std.resolve(len),
+    );
+
+    // stretched between both insets, or shrunk to fit otherwise
+    let constraint = |start: Option<f32>, end: Option<f32>, available: f32| {
+        let available = (available - start.unwrap_or(0.0) - end.unwrap_or(0.0)).max(0.0);
+        if start.is_some() && end.is_some() {
+            SpaceConstraint::Exact(available)
+        } else {
+            SpaceConstraint::Available(available)
+        }
+    };
+
+    let size = element.compute_layout_cached(LayoutInput {
+        constraint: Size {
+            width: constraint(left, right, containing_block.width),
+            height: constraint(top, bottom, containing_block.height),
+        },
+        length_standard,
+        direction,
+    });
+
+    let common = element.common_mut();
+    common.layout_output.location = Point {
+        x: offset(
+            left,
+            right,
+            size.width,
+            containing_block.width,
+            direction.is_rtl(),
+        ),
+        y: offset(top, bottom, size.height, containing_block.height, false),
+    };
+    if common.position() == Position::Absolute {
+        common.containing_block = Some(containing_block);
+    }
+    element.set_layout_completed();
+}
+
+/// Offset from the start side, at the inline start if there are no insets.
+fn offset(start: Option<f32>, end: Option<f32>, len: f32, available: f32, from_end: bool) -> f32 {
+    match (start, end) {
+        (Some(start), _) => start,
+        (None, Some(end)) => available - end - len,
+        (None, None) if from_end => available - len,
+        (None, None) => 0.0,
+    }
+}
+
+#[test]
+fn test_defer_again() {
+    let blocks = ContainingBlocks::new();
+
+    // the containing block is not being laid out
+    blocks.defer_again(&[1]);
+    assert_eq!(blocks.defer(2), Some(2));
+
+    blocks.push_frame();
+    assert_eq!(blocks.defer(3), None);
+    // a nested containing block lays out its own
+    blocks.push_frame();
+    blocks.defer_again(&[4]);
+    assert_eq!(blocks.pop_frame(), vec![4]);
+    // a child reused from the cache registers its descendants again
+    blocks.defer_again(&[1, 2]);
+    assert_eq!(blocks.pop_frame(), vec![3, 1, 2]);
+}
@@ -0,0 +1,322 @@
+use std::{path::PathBuf, rc::Rc};
+
+use irisia_backend::skia_safe::{
+    BlendMode, ClipOp, Color, FontMgr, Paint, Rect as SkRect,
+    canvas::SaveLayerRec,
+    color_filters,
+    svg::{self, Dom, LengthUnit},
+};
+use irisia_macros::style;
+
+use super::{
+    Common, EMCreateCtx, EventCallback, RenderArgs, RenderTree, Size, WeakElement,
+    layout::{LayoutInput, fit_intrinsic_size},
+    read_or_default,
+};
+use crate as irisia;
+use crate::{hook::Signal, primitive::Point};
+
+#[derive(Clone)]
+pub enum SvgSource {
+    Str(Rc<str>),
+    Bytes(Rc<[u8]>),
+    /// Read synchronously when the source is set, keep the files small.
+    Path(PathBuf),
+}
+
+#[style(SvgStyleExt)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct SvgStyle {
+    pub preserve_aspect_ratio: PreserveAspectRatio,
+    /// Paint every visible pixel with this color, for monochrome icons.
+    pub fill: Option<Color>,
+}
+
+impl SvgStyle {
+    pub const DEFAULT: Self = Self {
+        preserve_aspect_ratio: PreserveAspectRatio::Meet(Point::all(AxisAlign::Mid)),
+        fill: None,
+    };
+}
+
+impl Default for SvgStyle {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// How the view box is scaled into the layout box, like the attribute of the
+/// same name in svg.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PreserveAspectRatio {
+    /// Stretch to the box.
+    None,
+    /// Scale to fit in the box, the whole image is visible.
+    Meet(Point<AxisAlign>),
+    /// Scale to cover the box, the overflowed part is clipped.
+    Slice(Point<AxisAlign>),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AxisAlign {
+    Min,
+    Mid,
+    Max,
+}
+
+impl AxisAlign {
+    fn factor(self) -> f32 {
+        match self {
+            Self::Min => 0.0,
+            Self::Mid => 0.5,
+            Self::Max => 1.0,
+        }
+    }
+}
+
+impl PreserveAspectRatio {
+    /// Returns the scale and the offset that place `content` into `target`.
+    fn place(self, content: Size<f32>, target: Size<f32>) -> (Size<f32>, Point<f32>) {
+        let scale_x = target.width / content.width;
+        let scale_y = target.height / content.height;
+
+        let (scale, align) = match self {
+            Self::None => {
+                return (
+                    Size {
+                        width: scale_x,
+                        height: scale_y,
+                    },
+                    Point::all(0.0),
+                );
+            }
+            Self::Meet(align) => (scale_x.min(scale_y), align),
+            Self::Slice(align) => (scale_x.max(scale_y), align),
+        };
+
+        let offset = Point {
+            x: align.x.factor() * (target.width - content.width * scale),
+            y: align.y.factor() * (target.height - content.height * scale),
+        };
+        (Size::all(scale), offset)
+    }
+}
+
+struct LoadedSvg {
+    dom: Dom,
+    intrinsic_size: Option<Size<f32>>,
+}
+
+pub struct RenderSvg {
+    source: Option<Signal<SvgSource>>,
+    style: Option<Signal<SvgStyle>>,
+    svg: Option<LoadedSvg>,
+    common: Common,
+}
+
+impl RenderSvg {
+    pub fn new(
+        this: WeakElement,
+        source: Option<Signal<SvgSource>>,
+        style: Option<Signal<SvgStyle>>,
+        event_callback: Option<EventCallback>,
+        ctx: &EMCreateCtx,
+    ) -> Self {
+        let svg = source.as_ref().and_then(|source| load(&source.read()));
+        Self {
+            source,
+            style,
+            svg,
+            common: Common::new(this, event_callback, ctx),
+        }
+    }
+
+    pub fn source_updated(&mut self) {
+        self.svg = self.source.as_ref().and_then(|source| load(&source.read()));
+        self.common.request_reflow();
+        self.common.request_repaint();
+    }
+
+    pub fn style_updated(&mut self) {
+        self.common.request_repaint();
+    }
+}
+
+fn load(source: &SvgSource) -> Option<LoadedSvg> {
+    let file;
+    let bytes: &[u8] = match source {
+        SvgSource::Str(s) => s.as_bytes(),
+        SvgSource::Bytes(bytes) => bytes,
+        SvgSource::Path(path) => match std::fs::read(path) {
+            Ok(content) => {
+                file = content;
+                &file
+            }
+            Err(err) => {
+                crate::warning!("cannot read svg file {:?}: {}", path, err);
+                return None;
+            }
+        },
+    };
+
+    let dom = match Dom::from_bytes(bytes, FontMgr::new()) {
+        Ok(dom) => dom,
+        Err(err) => {
+            crate::error!("cannot parse svg: {}", err);
+            return None;
+        }
+    };
+
+    Some(LoadedSvg {
+        intrinsic_size: intrinsic_size(&dom),
+        dom,
+    })
+}
+
+impl RenderTree for RenderSvg {
+    fn render(&mut self, args: RenderArgs, draw_location: Point<f32>) {
+        let Some(svg) = &mut self.svg else {
+            return;
+        };
+
+        let style = read_or_default(&self.style, &SvgStyle::DEFAULT);
+        let size = self.common.layout_output.size;
+        let box_rect = SkRect::from_xywh(draw_location.x, draw_location.y, size.width, size.height);
+
+        let canvas = args.canvas;
+        let save_count = canvas.save();
+        canvas.clip_rect(box_rect, ClipOp::Intersect, true);
+
+        if let Some(fill) = style.fill {
+            let mut paint = Paint::default();
+            paint.set_color_filter(color_filters::blend(fill, BlendMode::SrcIn));
+            canvas.save_layer(&SaveLayerRec::default().bounds(&box_rect).paint(&paint));
+        }
+
+        canvas.translate((box_rect.left, box_rect.top));
+        match svg.intrinsic_size {
+            Some(intrinsic) => {
+                let (scale, offset) = style.preserve_aspect_ratio.place(intrinsic, size);
+                canvas.translate((offset.x, offset.y));
+                canvas.scale((scale.width, scale.height));
+                svg.dom
+                    .set_container_size((intrinsic.width, intrinsic.height));
+            }
+            None => svg.dom.set_container_size((size.width, size.height)),
+        }
+
+        svg.dom.render(canvas);
+        canvas.restore_to_count(save_count);
+    }
+
+    fn compute_layout(
+        &mut self,
+        LayoutInput {
+            constraint,
+            length_standard,
+            direction: _,
+        }: LayoutInput,
+    ) -> Size<f32> {
+        let dpi = length_standard.width.global.dpi;
+        let intrinsic = match self.svg.as_ref().and_then(|svg| svg.intrinsic_size) {
+            Some(size) => size.map(|x| x * dpi),
+            None => Size::all(0.0),
+        };
+        fit_intrinsic_size(constraint, intrinsic)
+    }
+
+    fn children_emit_event(&mut self, _: &mut super::EmitEventArgs) {}
+
+    fn common_mut(&mut self) -> &mut Common {
+        &mut self.common
+    }
+
+    fn common(&self) -> &Common {
+        &self.common
+    }
+}
+
+/// Absolute `width` and `height` of the root element, or the size of its `viewBox`.
+fn intrinsic_size(dom: &Dom) -> Option<Size<f32>> {
+    let root = dom.root();
+    let view_box = root
+        .view_box()
+        .filter(|view_box| view_box.width() > 0.0 && view_box.height() > 0.0)
+        .map(|view_box| Size {
+            width: view_box.width(),
+            height: view_box.height(),
+        });
+
+    match (absolute(root.width()), absolute(root.height()), view_box) {
+        (Some(width), Some(height), _) => Some(Size { width, height }),
+        (Some(width), None, Some(vb)) => Some(Size {
+            width,
+            height: width * vb.height / vb.width,
+        }),
+        (None, Some(height), Some(vb)) => Some(Size {
+            width: height * vb.width / vb.height,
+            height,
+        }),
+        (_, _, view_box) => view_box,
+    }
+}
+
+/// Resolved in pixels the way skia draws it, at 90 pixels per inch. `None` for
+/// percentages and the font relative units skia doesn't support.
+fn absolute(length: &svg::Length) -> Option<f32> {
+    const DPI: f32 = 90.0;
+    let inches = match length.unit {
+        LengthUnit::Number | LengthUnit::PX => return Some(length.value),
+        LengthUnit::IN => 1.0,
+        LengthUnit::CM => 1.0 / 2.54,
+        LengthUnit::MM => 1.0 / 25.4,
+        LengthUnit::PT => 1.0 / 72.272,
+        LengthUnit::PC => 12.0 / 72.272,
+        _ => return None,
+    };
+    Some(length.value * inches * DPI)
+}
+
+#[test]
+fn test_intrinsic_size() {
+    let size = |attrs: &str| {
+        let svg = format!(r#"<svg xmlns="http://www.w3.org/2000/svg" {attrs}/>"#);
+        let dom = Dom::from_str(&svg, FontMgr::new()).unwrap();
+        intrinsic_size(&dom).map(|s| (s.width, s.height))
+    };
+    // units other than pixels are not exact in floats
+    let close = |size: Option<(f32, f32)>, expected: (f32, f32)| {
+        let (width, height) = size.unwrap();
+        assert!(
+            (width - expected.0).abs() < 1e-3,
+            "{width} != {}",
+            expected.0
+        );
+        assert!(
+            (height - expected.1).abs() < 1e-3,
+            "{height} != {}",
+            expected.1
+        );
+    };
+
+    // only the view box
+    assert_eq!(size(r#"viewBox="0 0 24 12""#), Some((24.0, 12.0)));
+
+    // a missing or relative side follows the ratio of the view box
+    assert_eq!(
+        size(r#"width="48" viewBox="0 0 24 12""#),
+        Some((48.0, 24.0))
+    );
+    assert_eq!(
+        size(r#"width="100%" height="32" viewBox="0 0 16 8""#),
+        Some((64.0, 32.0))
+    );
+    assert_eq!(size(r#"width="48""#), None);
+    assert_eq!(size(r#"width="100%" height="32""#), None);
+
+    // units
+    assert_eq!(size(r#"width="24px" height="12""#), Some((24.0, 12.0)));
+    close(size(r#"width="1in" height="25.4mm""#), (90.0, 90.0));
+    close(size(r#"width="2.54cm" height="72.272pt""#), (90.0, 90.0));
+    assert_eq!(size(r#"width="2em" height="2em""#), None);
+}
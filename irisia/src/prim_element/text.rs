@@ -1,18 +1,22 @@
 use irisia_backend::skia_safe::{
     Color, FontMgr,
     textlayout::{
-        FontCollection, Paragraph, ParagraphBuilder, ParagraphStyle, TextStyle as SkTextStyle,
+        FontCollection, Paragraph, ParagraphBuilder, ParagraphStyle, TextDirection,
+        TextStyle as SkTextStyle,
     },
 };
 use irisia_macros::style;
 
 use super::{
     Common, EMCreateCtx, EventCallback, RenderTree, Size, WeakElement,
-    layout::{LayoutInput, SpaceConstraint},
+    layout::{Direction, LayoutInput, SpaceConstraint},
     read_or_default,
 };
 use crate as irisia;
-use crate::{hook::Signal, primitive::Point};
+use crate::{
+    hook::Signal,
+    primitive::{Length, Point, length::LengthStandardGlobalPart},
+};
 
 pub type SignalStr = Signal<dyn AsRef<str>>;
 
@@ -20,19 +24,24 @@ pub struct RenderText {
     text: Option<SignalStr>,
     style: Option<Signal<TextStyle>>,
     paragraph: Option<Paragraph>,
+    direction: Direction,
+    /// Font size in physical pixels the paragraph is built at.
+    font_size: f32,
     common: Common,
 }
 
-#[style(TextStyleExt)]
+#[style(TextStyleExt, interpolate)]
 #[derive(PartialEq, Clone)]
 pub struct TextStyle {
-    pub font_size: f32,
+    /// `em` and percentages of it refer to the inherited font size, auto
+    /// inherits it.
+    pub font_size: Length,
     pub font_color: Color,
 }
 
 impl TextStyle {
     pub const DEFAULT: Self = Self {
-        font_size: 20.0,
+        font_size: Length::Auto,
         font_color: Color::BLACK,
     };
 }
@@ -55,6 +64,8 @@ impl RenderText {
             text,
             style,
             paragraph: None,
+            direction: Direction::Ltr,
+            font_size: LengthStandardGlobalPart::DEFAULT_ROOT_FONT_SIZE,
             common: Common::new(this, event_callback, ctx),
         }
     }
@@ -68,9 +79,15 @@ impl RenderText {
 
 impl RenderTree for RenderText {
     fn render(&mut self, args: super::RenderArgs, draw_location: Point<f32>) {
-        let paragraph = self
-            .paragraph
-            .get_or_insert_with(|| build_paragraph(&self.text, &self.style));
+        let paragraph = self.paragraph.get_or_insert_with(|| {
+            build_paragraph(&self.text, &self.style, self.direction, self.font_size)
+        });
+
+        // measuring may have wrapped it to another width since the final layout
+        let width = self.common.layout_output.size.width;
+        if paragraph.max_width() != width {
+            paragraph.layout(width);
+        }
 
         paragraph.paint(args.canvas, draw_location);
     }
@@ -79,12 +96,25 @@ impl RenderTree for RenderText {
         &mut self,
         LayoutInput {
             constraint,
-            length_standard: _,
+            length_standard,
+            direction,
         }: LayoutInput,
     ) -> Size<f32> {
+        let inherited_font_size = length_standard.width.font_size;
+        let font_size = length_standard
+            .width
+            .set_percentage_reference(inherited_font_size)
+            .resolve(read_or_default(&self.style, &TextStyle::DEFAULT).font_size)
+            .unwrap_or(inherited_font_size);
+        if self.direction != direction || self.font_size != font_size {
+            self.direction = direction;
+            self.font_size = font_size;
+            self.paragraph = None;
+        }
+
         let paragraph = self
             .paragraph
-            .get_or_insert_with(|| build_paragraph(&self.text, &self.style));
+            .get_or_insert_with(|| build_paragraph(&self.text, &self.style, direction, font_size));
 
         let w = match constraint.width {
             SpaceConstraint::Exact(width) | SpaceConstraint::Available(width) => width,
@@ -110,16 +140,27 @@ impl RenderTree for RenderText {
     fn children_emit_event(&mut self, _: &mut super::EmitEventArgs) {}
 }
 
-fn build_paragraph(text: &Option<SignalStr>, style: &Option<Signal<TextStyle>>) -> Paragraph {
+fn build_paragraph(
+    text: &Option<SignalStr>,
+    style: &Option<Signal<TextStyle>>,
+    direction: Direction,
+    font_size: f32,
+) -> Paragraph {
     let mut font_collection = FontCollection::new();
     font_collection.set_default_font_manager(FontMgr::new(), None);
 
+    let mut paragraph_style = ParagraphStyle::new();
+    paragraph_style.set_text_direction(match direction {
+        Direction::Ltr => TextDirection::LTR,
+        Direction::Rtl => TextDirection::RTL,
+    });
+
     let style = read_or_default(style, &TextStyle::DEFAULT);
-    ParagraphBuilder::new(&ParagraphStyle::new(), font_collection)
+    ParagraphBuilder::new(&paragraph_style, font_collection)
         .push_style(
             SkTextStyle::new()
                 .set_color(style.font_color)
-                .set_font_size(style.font_size),
+                .set_font_size(font_size),
         )
         .add_text(read_or_default(text, &"").as_ref())
         .build()
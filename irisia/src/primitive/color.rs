@@ -0,0 +1,343 @@
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use irisia_backend::skia_safe::Color;
+
+use super::parse::{Cursor, ParseError};
+
+/// A [`Color`] parsed from or written as a string. Parses css colors like
+/// `#rrggbbaa`, `#rgb`, `rgb(255 0 0 / 50%)`, `rgba(255, 0, 0, 0.5)`,
+/// `hsl(120deg, 100%, 50%)` and named colors, writes `#rrggbbaa`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ColorValue(pub Color);
+
+impl From<Color> for ColorValue {
+    fn from(value: Color) -> Self {
+        Self(value)
+    }
+}
+
+impl From<ColorValue> for Color {
+    fn from(value: ColorValue) -> Self {
+        value.0
+    }
+}
+
+impl Display for ColorValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let c = self.0;
+        write!(f, "#{:02x}{:02x}{:02x}{:02x}", c.r(), c.g(), c.b(), c.a())
+    }
+}
+
+impl FromStr for ColorValue {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cursor = Cursor::new(s);
+        let start = cursor.token_start();
+
+        let color = if cursor.eat("#") {
+            hex(&mut cursor, start)?
+        } else if cursor.eat("rgba(") || cursor.eat("rgb(") {
+            rgb(&mut cursor)?
+        } else if cursor.eat("hsla(") || cursor.eat("hsl(") {
+            hsl(&mut cursor)?
+        } else {
+            let name = cursor.ident().to_ascii_lowercase();
+            match NAMED_COLORS.binary_search_by_key(&name.as_str(), |&(name, _)| name) {
+                Ok(index) => NAMED_COLORS[index].1,
+                Err(_) => return Err(cursor.error_at(start, "unknown color")),
+            }
+        };
+
+        cursor.finish()?;
+        Ok(Self(color))
+    }
+}
+
+fn hex(cursor: &mut Cursor, start: usize) -> Result<Color, ParseError> {
+    let digits = cursor.hex_digits();
+    let digit = |index: usize| u8::from_str_radix(&digits[index..index + 1], 16).unwrap();
+    let byte = |index: usize| u8::from_str_radix(&digits[index..index + 2], 16).unwrap();
+
+    Ok(match digits.len() {
+        3 => Color::from_argb(255, digit(0) * 17, digit(1) * 17, digit(2) * 17),
+        4 => Color::from_argb(digit(3) * 17, digit(0) * 17, digit(1) * 17, digit(2) * 17),
+        6 => Color::from_argb(255, byte(0), byte(2), byte(4)),
+        8 => Color::from_argb(byte(6), byte(0), byte(2), byte(4)),
+        _ => return Err(cursor.error_at(start, "expected 3, 4, 6 or 8 hex digits")),
+    })
+}
+
+fn rgb(cursor: &mut Cursor) -> Result<Color, ParseError> {
+    let ([r, g, b], alpha) = arguments(cursor)?;
+    let channel = |c: Component| -> Result<u8, ParseError> {
+        let value = match c.unit {
+            "" => c.value,
+            "%" => c.value * 2.55,
+            _ => return Err(cursor.error_at(c.position, "expected a number or a percentage")),
+        };
+        Ok(value.clamp(0.0, 255.0).round() as u8)
+    };
+
+    Ok(Color::from_argb(
+        alpha_channel(cursor, alpha)?,
+        channel(r)?,
+        channel(g)?,
+        channel(b)?,
+    ))
+}
+
+fn hsl(cursor: &mut Cursor) -> Result<Color, ParseError> {
+    let ([h, s, l], alpha) = arguments(cursor)?;
+    let hue = match h.unit {
+        "" | "deg" => h.value,
+        "turn" => h.value * 360.0,
+        _ => return Err(cursor.error_at(h.position, "expected an angle")),
+    };
+    let fraction = |c: Component| match c.unit {
+        // a plain number is taken as a percentage too
+        "" | "%" => Ok((c.value / 100.0).clamp(0.0, 1.0)),
+        _ => Err(cursor.error_at(c.position, "expected a percentage")),
+    };
+    let (s, l) = (fraction(s)?, fraction(l)?);
+
+    let f = |n: f32| {
+        let k = (n + hue.rem_euclid(360.0) / 30.0) % 12.0;
+        let value = l - s * l.min(1.0 - l) * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0);
+        (value * 255.0).round() as u8
+    };
+
+    Ok(Color::from_argb(
+        alpha_channel(cursor, alpha)?,
+        f(0.0),
+        f(8.0),
+        f(4.0),
+    ))
+}
+
+#[derive(Clone, Copy)]
+struct Component<'a> {
+    value: f32,
+    unit: &'a str,
+    position: usize,
+}
+
+fn component<'a>(cursor: &mut Cursor<'a>) -> Result<Component<'a>, ParseError> {
+    let position = cursor.token_start();
+    let negative = cursor.eat("-");
+    let value = cursor.number()?;
+    Ok(Component {
+        value: if negative { -value } else { value },
+        unit: cursor.ident(),
+        position,
+    })
+}
+
+/// Three components and an optional alpha closed by `)`, in either
+/// `a, b, c, alpha` or `a b c / alpha`.
+fn arguments<'a>(
+    cursor: &mut Cursor<'a>,
+) -> Result<([Component<'a>; 3], Option<Component<'a>>), ParseError> {
+    let first = component(cursor)?;
+    let commas = cursor.eat(",");
+    let second = component(cursor)?;
+    if commas {
+        cursor.expect(",", "expected `,`")?;
+    }
+    let third = component(cursor)?;
+
+    let alpha = if cursor.eat(if commas { "," } else { "/" }) {
+        Some(component(cursor)?)
+    } else {
+        None
+    };
+    cursor.expect(")", "expected `)`")?;
+
+    Ok(([first, second, third], alpha))
+}
+
+fn alpha_channel(cursor: &Cursor, alpha: Option<Component>) -> Result<u8, ParseError> {
+    let Some(alpha) = alpha else {
+        return Ok(255);
+    };
+    let value = match alpha.unit {
+        "" => alpha.value,
+        "%" => alpha.value / 100.0,
+        _ => return Err(cursor.error_at(alpha.position, "expected a number or a percentage")),
+    };
+    Ok((value.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+/// Css named colors, sorted by name.
+const NAMED_COLORS: &[(&str, Color)] = &[
+    ("aliceblue", Color::new(0xfff0f8ff)),
+    ("antiquewhite", Color::new(0xfffaebd7)),
+    ("aqua", Color::new(0xff00ffff)),
+    ("aquamarine", Color::new(0xff7fffd4)),
+    ("azure", Color::new(0xfff0ffff)),
+    ("beige", Color::new(0xfff5f5dc)),
+    ("bisque", Color::new(0xffffe4c4)),
+    ("black", Color::new(0xff000000)),
+    ("blanchedalmond", Color::new(0xffffebcd)),
+    ("blue", Color::new(0xff0000ff)),
+    ("blueviolet", Color::new(0xff8a2be2)),
+    ("brown", Color::new(0xffa52a2a)),
+    ("burlywood", Color::new(0xffdeb887)),
+    ("cadetblue", Color::new(0xff5f9ea0)),
+    ("chartreuse", Color::new(0xff7fff00)),
+    ("chocolate", Color::new(0xffd2691e)),
+    ("coral", Color::new(0xffff7f50)),
+    ("cornflowerblue", Color::new(0xff6495ed)),
+    ("cornsilk", Color::new(0xfffff8dc)),
+    ("crimson", Color::new(0xffdc143c)),
+    ("cyan", Color::new(0xff00ffff)),
+    ("darkblue", Color::new(0xff00008b)),
+    ("darkcyan", Color::new(0xff008b8b)),
+    ("darkgoldenrod", Color::new(0xffb8860b)),
+    ("darkgray", Color::new(0xffa9a9a9)),
+    ("darkgreen", Color::new(0xff006400)),
+    ("darkgrey", Color::new(0xffa9a9a9)),
+    ("darkkhaki", Color::new(0xffbdb76b)),
+    ("darkmagenta", Color::new(0xff8b008b)),
+    ("darkolivegreen", Color::new(0xff556b2f)),
+    ("darkorange", Color::new(0xffff8c00)),
+    ("darkorchid", Color::new(0xff9932cc)),
+    ("darkred", Color::new(0xff8b0000)),
+    ("darksalmon", Color::new(0xffe9967a)),
+    ("darkseagreen", Color::new(0xff8fbc8f)),
+    ("darkslateblue", Color::new(0xff483d8b)),
+    ("darkslategray", Color::new(0xff2f4f4f)),
+    ("darkslategrey", Color::new(0xff2f4f4f)),
+    ("darkturquoise", Color::new(0xff00ced1)),
+    ("darkviolet", Color::new(0xff9400d3)),
+    ("deeppink", Color::new(0xffff1493)),
+    ("deepskyblue", Color::new(0xff00bfff)),
+    ("dimgray", Color::new(0xff696969)),
+    ("dimgrey", Color::new(0xff696969)),
+    ("dodgerblue", Color::new(0xff1e90ff)),
+    ("firebrick", Color::new(0xffb22222)),
+    ("floralwhite", Color::new(0xfffffaf0)),
+    ("forestgreen", Color::new(0xff228b22)),
+    ("fuchsia", Color::new(0xffff00ff)),
+    ("gainsboro", Color::new(0xffdcdcdc)),
+    ("ghostwhite", Color::new(0xfff8f8ff)),
+    ("gold", Color::new(0xffffd700)),
+    ("goldenrod", Color::new(0xffdaa520)),
+    ("gray", Color::new(0xff808080)),
+    ("green", Color::new(0xff008000)),
+    ("greenyellow", Color::new(0xffadff2f)),
+    ("grey", Color::new(0xff808080)),
+    ("honeydew", Color::new(0xfff0fff0)),
+    ("hotpink", Color::new(0xffff69b4)),
+    ("indianred", Color::new(0xffcd5c5c)),
+    ("indigo", Color::new(0xff4b0082)),
+    ("ivory", Color::new(0xfffffff0)),
+    ("khaki", Color::new(0xfff0e68c)),
+    ("lavender", Color::new(0xffe6e6fa)),
+    ("lavenderblush", Color::new(0xfffff0f5)),
+    ("lawngreen", Color::new(0xff7cfc00)),
+    ("lemonchiffon", Color::new(0xfffffacd)),
+    ("lightblue", Color::new(0xffadd8e6)),
+    ("lightcoral", Color::new(0xfff08080)),
+    ("lightcyan", Color::new(0xffe0ffff)),
+    ("lightgoldenrodyellow", Color::new(0xfffafad2)),
+    ("lightgray", Color::new(0xffd3d3d3)),
+    ("lightgreen", Color::new(0xff90ee90)),
+    ("lightgrey", Color::new(0xffd3d3d3)),
+    ("lightpink", Color::new(0xffffb6c1)),
+    ("lightsalmon", Color::new(0xffffa07a)),
+    ("lightseagreen", Color::new(0xff20b2aa)),
+    ("lightskyblue", Color::new(0xff87cefa)),
+    ("lightslategray", Color::new(0xff778899)),
+    ("lightslategrey", Color::new(0xff778899)),
+    ("lightsteelblue", Color::new(0xffb0c4de)),
+    ("lightyellow", Color::new(0xffffffe0)),
+    ("lime", Color::new(0xff00ff00)),
+    ("limegreen", Color::new(0xff32cd32)),
+    ("linen", Color::new(0xfffaf0e6)),
+    ("magenta", Color::new(0xffff00ff)),
+    ("maroon", Color::new(0xff800000)),
+    ("mediumaquamarine", Color::new(0xff66cdaa)),
+    ("mediumblue", Color::new(0xff0000cd)),
+    ("mediumorchid", Color::new(0xffba55d3)),
+    ("mediumpurple", Color::new(0xff9370db)),
+    ("mediumseagreen", Color::new(0xff3cb371)),
+    ("mediumslateblue", Color::new(0xff7b68ee)),
+    ("mediumspringgreen", Color::new(0xff00fa9a)),
+    ("mediumturquoise", Color::new(0xff48d1cc)),
+    ("mediumvioletred", Color::new(0xffc71585)),
+    ("midnightblue", Color::new(0xff191970)),
+    ("mintcream", Color::new(0xfff5fffa)),
+    ("mistyrose", Color::new(0xffffe4e1)),
+    ("moccasin", Color::new(0xffffe4b5)),
+    ("navajowhite", Color::new(0xffffdead)),
+    ("navy", Color::new(0xff000080)),
+    ("oldlace", Color::new(0xfffdf5e6)),
+    ("olive", Color::new(0xff808000)),
+    ("olivedrab", Color::new(0xff6b8e23)),
+    ("orange", Color::new(0xffffa500)),
+    ("orangered", Color::new(0xffff4500)),
+    ("orchid", Color::new(0xffda70d6)),
+    ("palegoldenrod", Color::new(0xffeee8aa)),
+    ("palegreen", Color::new(0xff98fb98)),
+    ("paleturquoise", Color::new(0xffafeeee)),
+    ("palevioletred", Color::new(0xffdb7093)),
+    ("papayawhip", Color::new(0xffffefd5)),
+    ("peachpuff", Color::new(0xffffdab9)),
+    ("peru", Color::new(0xffcd853f)),
+    ("pink", Color::new(0xffffc0cb)),
+    ("plum", Color::new(0xffdda0dd)),
+    ("powderblue", Color::new(0xffb0e0e6)),
+    ("purple", Color::new(0xff800080)),
+    ("rebeccapurple", Color::new(0xff663399)),
+    ("red", Color::new(0xffff0000)),
+    ("rosybrown", Color::new(0xffbc8f8f)),
+    ("royalblue", Color::new(0xff4169e1)),
+    ("saddlebrown", Color::new(0xff8b4513)),
+    ("salmon", Color::new(0xfffa8072)),
+    ("sandybrown", Color::new(0xfff4a460)),
+    ("seagreen", Color::new(0xff2e8b57)),
+    ("seashell", Color::new(0xfffff5ee)),
+    ("sienna", Color::new(0xffa0522d)),
+    ("silver", Color::new(0xffc0c0c0)),
+    ("skyblue", Color::new(0xff87ceeb)),
+    ("slateblue", Color::new(0xff6a5acd)),
+    ("slategray", Color::new(0xff708090)),
+    ("slategrey", Color::new(0xff708090)),
+    ("snow", Color::new(0xfffffafa)),
+    ("springgreen", Color::new(0xff00ff7f)),
+    ("steelblue", Color::new(0xff4682b4)),
+    ("tan", Color::new(0xffd2b48c)),
+    ("teal", Color::new(0xff008080)),
+    ("thistle", Color::new(0xffd8bfd8)),
+    ("tomato", Color::new(0xffff6347)),
+    ("transparent", Color::new(0x00000000)),
+    ("turquoise", Color::new(0xff40e0d0)),
+    ("violet", Color::new(0xffee82ee)),
+    ("wheat", Color::new(0xfff5deb3)),
+    ("white", Color::new(0xffffffff)),
+    ("whitesmoke", Color::new(0xfff5f5f5)),
+    ("yellow", Color::new(0xffffff00)),
+    ("yellowgreen", Color::new(0xff9acd32)),
+];
+
+#[test]
+fn test_parse_color() {
+    let parse = |s: &str| s.parse::<ColorValue>().map(|c| c.to_string());
+
+    assert_eq!(parse("#f80").unwrap(), "#ff8800ff");
+    assert_eq!(parse("#12345678").unwrap(), "#12345678");
+    assert_eq!(parse("rgb(255, 0, 0)").unwrap(), "#ff0000ff");
+    assert_eq!(parse("rgba(0 0 100% / 50%)").unwrap(), "#0000ff80");
+    assert_eq!(parse("hsl(120deg, 100%, 50%)").unwrap(), "#00ff00ff");
+    assert_eq!(parse(" RebeccaPurple ").unwrap(), "#663399ff");
+    assert_eq!(parse("transparent").unwrap(), "#00000000");
+
+    assert_eq!(parse("#12345").unwrap_err().position, 0);
+    assert_eq!(parse("rgb(1, 2 3)").unwrap_err().position, 9);
+    assert_eq!(parse("reddish").unwrap_err().position, 0);
+}
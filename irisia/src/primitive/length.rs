@@ -1,28 +1,48 @@
 use core::str;
 use std::{
-    fmt::{Debug, Formatter},
+    fmt::{Debug, Display, Formatter},
     io::{BufWriter, Write as _},
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
 use super::size::Size;
+use crate::animation::{Interpolate, discrete};
 
 #[derive(Clone, Copy, PartialEq)]
 pub struct LengthStandard {
     pub global: LengthStandardGlobalPart,
     pub percentage_reference: f32,
+    /// Inherited font size in physical pixels, which `em` refers to.
+    pub font_size: f32,
 }
 
 #[derive(Clone, Copy, PartialEq)]
 pub struct LengthStandardGlobalPart {
     pub dpi: f32,
     pub viewport_size: Size<u32>,
+    /// Font size of the roots of the window in logical pixels, which `rem`
+    /// refers to.
+    pub root_font_size: f32,
+}
+
+impl LengthStandardGlobalPart {
+    pub const DEFAULT_ROOT_FONT_SIZE: f32 = 16.0;
+
+    /// The standard of the roots of the window, which have the root font size.
+    pub fn root_standard(self, percentage_reference: f32) -> LengthStandard {
+        LengthStandard {
+            global: self,
+            percentage_reference,
+            font_size: self.root_font_size * self.dpi,
+        }
+    }
 }
 
 impl LengthStandard {
     pub fn resolve(&self, length: Length) -> Option<f32> {
         match length {
             Length::Measured(m) => Some(m.to_resolved(self)),
+            Length::Expr(expr) => Some(expr.to_resolved(self)),
             Length::Auto => None,
         }
     }
@@ -33,8 +53,8 @@ impl LengthStandard {
 
     pub fn set_percentage_reference(&self, r: f32) -> Self {
         Self {
-            global: self.global,
             percentage_reference: r,
+            ..*self
         }
     }
 }
@@ -44,20 +64,286 @@ pub enum Length {
     #[default]
     Auto,
     Measured(MeasuredLength),
+    /// Built by [`Length::min`], [`Length::max`] and [`Length::clamp`], and
+    /// by adding to one of them.
+    Expr(LengthExpr),
 }
 
 impl Length {
     pub const ZERO: Self = Length::Measured(MeasuredLength::ZERO);
 
-    pub fn map<F>(self, f: F) -> Length
+    /// Map every measured part.
+    pub fn map<F>(self, mut f: F) -> Length
     where
-        F: FnOnce(MeasuredLength) -> MeasuredLength,
+        F: FnMut(MeasuredLength) -> MeasuredLength,
     {
         match self {
             Length::Auto => Length::Auto,
             Length::Measured(m) => Length::Measured(f(m)),
+            Length::Expr(expr) => Length::Expr(expr.map(f)),
         }
     }
+
+    /// The smaller one once resolved, like `min()` in css. `auto` doesn't
+    /// limit anything.
+    ///
+    /// Panics if the result has more than [`LengthExpr::MAX_TERMS`] terms,
+    /// see [`Length::checked_min`].
+    pub fn min(self, other: Self) -> Self {
+        self.checked_min(other).expect(TOO_MANY_TERMS)
+    }
+
+    /// The larger one once resolved, like `max()` in css. `auto` doesn't
+    /// limit anything.
+    ///
+    /// Panics if the result has more than [`LengthExpr::MAX_TERMS`] terms,
+    /// see [`Length::checked_max`].
+    pub fn max(self, other: Self) -> Self {
+        self.checked_max(other).expect(TOO_MANY_TERMS)
+    }
+
+    /// Limit the length between `min` and `max` once resolved, `min` wins if
+    /// they overlap, like `clamp()` in css.
+    ///
+    /// Panics if the result has more than [`LengthExpr::MAX_TERMS`] terms,
+    /// see [`Length::checked_clamp`].
+    pub fn clamp(self, min: Self, max: Self) -> Self {
+        self.checked_clamp(min, max).expect(TOO_MANY_TERMS)
+    }
+
+    /// [`Length::min`], or `None` if there would be too many terms.
+    pub fn checked_min(self, other: Self) -> Option<Self> {
+        self.combine(other, ExprOp::Min)
+    }
+
+    /// [`Length::max`], or `None` if there would be too many terms.
+    pub fn checked_max(self, other: Self) -> Option<Self> {
+        self.combine(other, ExprOp::Max)
+    }
+
+    /// [`Length::clamp`], or `None` if there would be too many terms.
+    pub fn checked_clamp(self, min: Self, max: Self) -> Option<Self> {
+        self.checked_min(max)?.checked_max(min)
+    }
+
+    /// The sum, or `None` if there would be too many terms.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.combine(other, ExprOp::Add)
+    }
+
+    fn combine(self, other: Self, op: ExprOp) -> Option<Self> {
+        Some(match (self, other, op) {
+            (Length::Measured(lhs), Length::Measured(rhs), ExprOp::Add) => {
+                Length::Measured(lhs.add(rhs))
+            }
+            // `auto` adds nothing and limits nothing
+            (Length::Auto, other, ExprOp::Add) => other,
+            (Length::Auto, _, _) => Length::Auto,
+            (this, Length::Auto, _) => this,
+            (lhs, rhs, op) => Length::Expr(lhs.to_expr().combine(&rhs.to_expr(), op)?),
+        })
+    }
+
+    fn to_expr(self) -> LengthExpr {
+        match self {
+            Length::Measured(m) => LengthExpr::operand(m),
+            Length::Expr(expr) => expr,
+            Length::Auto => unreachable!("`auto` is never combined"),
+        }
+    }
+}
+
+const TOO_MANY_TERMS: &str = "too many terms in a length expression";
+
+/// Lengths combined by `min()`, `max()` and additions, resolved lazily since
+/// lengths of different units are only comparable once resolved. It's kept
+/// inline in postfix so that `Length` is `Copy`, every non-zero unit of every
+/// operand takes one of the [`LengthExpr::MAX_TERMS`] terms.
+#[derive(Clone, Copy, PartialEq)]
+pub struct LengthExpr {
+    values: [f32; LengthExpr::MAX_TERMS],
+    /// Index of the unit of each value, [`LengthExpr::NEW_OPERAND`] marks the
+    /// first term of an operand.
+    units: [u8; LengthExpr::MAX_TERMS],
+    term_count: u8,
+    /// [`ExprOp::Operand`] pushes the next operand.
+    ops: [ExprOp; LengthExpr::MAX_OPS],
+    op_count: u8,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum ExprOp {
+    Operand,
+    Min,
+    Max,
+    Add,
+}
+
+impl LengthExpr {
+    /// `min(1px + 1em, 1pct)` has three terms.
+    pub const MAX_TERMS: usize = 12;
+    const MAX_OPS: usize = Self::MAX_TERMS * 2 - 1;
+    const NEW_OPERAND: u8 = 0x80;
+
+    const EMPTY: Self = Self {
+        values: [0.0; Self::MAX_TERMS],
+        units: [0; Self::MAX_TERMS],
+        term_count: 0,
+        ops: [ExprOp::Operand; Self::MAX_OPS],
+        op_count: 0,
+    };
+
+    fn operand(m: MeasuredLength) -> Self {
+        let mut expr = Self::EMPTY;
+        expr.op_count = 1;
+        expr.push_operand(m)
+            .expect("an operand always fits in an empty expression");
+        expr
+    }
+
+    /// Append the terms of an operand, `None` if they don't fit.
+    fn push_operand(&mut self, m: MeasuredLength) -> Option<()> {
+        let start = self.term_count as usize;
+        let mut end = start;
+        for (unit, value) in m.to_array().into_iter().enumerate() {
+            if value != 0.0 {
+                *self.values.get_mut(end)? = value;
+                self.units[end] = unit as u8;
+                end += 1;
+            }
+        }
+        // zero is `0px`
+        if end == start {
+            *self.values.get_mut(end)? = 0.0;
+            self.units[end] = 0;
+            end += 1;
+        }
+        self.units[start] |= Self::NEW_OPERAND;
+        self.term_count = end as u8;
+        Some(())
+    }
+
+    fn operands(&self) -> impl Iterator<Item = MeasuredLength> + '_ {
+        let count = self.term_count as usize;
+        let units = &self.units[..count];
+        (0..count)
+            .filter(|&start| units[start] & Self::NEW_OPERAND != 0)
+            .map(move |start| {
+                let mut fields = [0.0; MeasuredLength::UNIT_COUNT];
+                for term in start..count {
+                    if term != start && units[term] & Self::NEW_OPERAND != 0 {
+                        break;
+                    }
+                    fields[(units[term] & !Self::NEW_OPERAND) as usize] = self.values[term];
+                }
+                MeasuredLength::from_array(fields)
+            })
+    }
+
+    fn ops(&self) -> &[ExprOp] {
+        &self.ops[..self.op_count as usize]
+    }
+
+    /// `self` and `rhs` joined by `op`, `None` if there are too many terms.
+    fn combine(&self, rhs: &Self, op: ExprOp) -> Option<Self> {
+        let (lhs_terms, lhs_ops) = (self.term_count as usize, self.op_count as usize);
+        let term_count = lhs_terms + rhs.term_count as usize;
+        if term_count > Self::MAX_TERMS {
+            return None;
+        }
+        // a binary tree of n operands has n - 1 operators, and an operand has
+        // at least one term
+        let op_count = lhs_ops + rhs.ops().len() + 1;
+
+        let mut expr = *self;
+        expr.values[lhs_terms..term_count].copy_from_slice(&rhs.values[..rhs.term_count as usize]);
+        expr.units[lhs_terms..term_count].copy_from_slice(&rhs.units[..rhs.term_count as usize]);
+        expr.ops[lhs_ops..op_count - 1].copy_from_slice(rhs.ops());
+        expr.ops[op_count - 1] = op;
+        expr.term_count = term_count as u8;
+        expr.op_count = op_count as u8;
+        Some(expr)
+    }
+
+    /// Map every operand.
+    ///
+    /// Panics if the mapped operands have more than [`LengthExpr::MAX_TERMS`]
+    /// terms.
+    pub fn map<F>(self, mut f: F) -> Self
+    where
+        F: FnMut(MeasuredLength) -> MeasuredLength,
+    {
+        let mut expr = Self::EMPTY;
+        expr.ops = self.ops;
+        expr.op_count = self.op_count;
+        for operand in self.operands() {
+            expr.push_operand(f(operand)).expect(TOO_MANY_TERMS);
+        }
+        expr
+    }
+
+    /// Scaling by a negative number swaps the minimums and maximums.
+    fn scale(mut self, factor: f32) -> Self {
+        for value in &mut self.values[..self.term_count as usize] {
+            *value *= factor;
+        }
+
+        if factor < 0.0 {
+            for op in &mut self.ops[..self.op_count as usize] {
+                *op = match *op {
+                    ExprOp::Min => ExprOp::Max,
+                    ExprOp::Max => ExprOp::Min,
+                    op => op,
+                };
+            }
+        }
+        self
+    }
+
+    /// Evaluate the postfix, `T` is the value of a subexpression.
+    fn fold<T>(
+        &self,
+        mut operand: impl FnMut(MeasuredLength) -> T,
+        mut combine: impl FnMut(ExprOp, T, T) -> T,
+    ) -> T {
+        let mut stack: [Option<T>; Self::MAX_TERMS] = std::array::from_fn(|_| None);
+        let mut depth = 0;
+        let mut operands = self.operands();
+
+        for &op in self.ops() {
+            let value = match op {
+                ExprOp::Operand => operand(operands.next().expect("operands are too few")),
+                _ => {
+                    depth -= 2;
+                    let lhs = stack[depth].take().expect("operands are too few");
+                    let rhs = stack[depth + 1].take().expect("operands are too few");
+                    combine(op, lhs, rhs)
+                }
+            };
+            stack[depth] = Some(value);
+            depth += 1;
+        }
+
+        stack[0].take().expect("empty length expression")
+    }
+
+    pub fn to_resolved(&self, standard: &LengthStandard) -> f32 {
+        self.fold(
+            |m| m.to_resolved(standard),
+            |op, lhs, rhs| match op {
+                ExprOp::Min => lhs.min(rhs),
+                ExprOp::Max => lhs.max(rhs),
+                ExprOp::Add => lhs + rhs,
+                ExprOp::Operand => unreachable!(),
+            },
+        )
+    }
+
+    /// Whether both have the same operators and units, so they can be
+    /// interpolated.
+    fn same_shape(&self, other: &Self) -> bool {
+        self.ops() == other.ops() && self.units[..] == other.units[..]
+    }
 }
 
 macro_rules! create_length {
@@ -93,6 +379,16 @@ macro_rules! create_length {
                 self
             }
 
+            const UNIT_COUNT: usize = [$(stringify!($short),)*].len();
+
+            const fn to_array(self) -> [f32; Self::UNIT_COUNT] {
+                [$(self.$name,)*]
+            }
+
+            const fn from_array([$($name,)*]: [f32; Self::UNIT_COUNT]) -> Self {
+                Self { $($name,)* }
+            }
+
             /*fn fields(&mut self) -> impl Iterator<Item = &mut f32> {
                 [ $(&mut self.$name,)* ].into_iter()
             }*/
@@ -114,6 +410,8 @@ create_length! {
     viewport_min    VMIN vmin,
     viewport_max    VMAX vmax,
     percent         PCT  pct,
+    font_size       EM   em,
+    root_font_size  REM  rem,
 }
 
 impl Default for MeasuredLength {
@@ -128,7 +426,13 @@ impl MeasuredLength {
     pub fn to_resolved(&self, standard: &LengthStandard) -> f32 {
         let &LengthStandard {
             percentage_reference: parent_axis_len,
-            global: LengthStandardGlobalPart { dpi, viewport_size },
+            font_size,
+            global:
+                LengthStandardGlobalPart {
+                    dpi,
+                    viewport_size,
+                    root_font_size,
+                },
         } = standard;
 
         let vw = viewport_size.width as f32;
@@ -140,6 +444,8 @@ impl MeasuredLength {
             + self.viewport_min * vw.min(vh)
             + self.viewport_max * vw.max(vh)
             + self.percent * parent_axis_len
+            + self.font_size * font_size
+            + self.root_font_size * root_font_size * dpi
     }
 }
 
@@ -147,7 +453,11 @@ impl Mul<f32> for Length {
     type Output = Length;
 
     fn mul(self, rhs: f32) -> Self::Output {
-        self.map(|this| this.mul(rhs))
+        match self {
+            Length::Auto => Length::Auto,
+            Length::Measured(m) => Length::Measured(m.mul(rhs)),
+            Length::Expr(expr) => Length::Expr(expr.scale(rhs)),
+        }
     }
 }
 
@@ -155,7 +465,7 @@ impl Mul<Length> for i32 {
     type Output = Length;
 
     fn mul(self, rhs: Length) -> Self::Output {
-        rhs.map(|rhs| rhs.mul(self as _))
+        rhs * self as f32
     }
 }
 
@@ -163,7 +473,7 @@ impl Mul<Length> for f32 {
     type Output = Length;
 
     fn mul(self, rhs: Length) -> Self::Output {
-        rhs.map(|rhs| rhs.mul(self))
+        rhs * self
     }
 }
 
@@ -202,13 +512,11 @@ impl Neg for Length {
 impl Add<Self> for Length {
     type Output = Self;
 
+    /// Panics if the result has more than [`LengthExpr::MAX_TERMS`] terms,
+    /// see [`Length::checked_add`].
     #[inline]
     fn add(self, rhs: Self) -> Self::Output {
-        match (self, rhs) {
-            (Length::Measured(lhs), Length::Measured(rhs)) => Length::Measured(lhs.add(rhs)),
-            (Length::Measured(v), _) | (_, Length::Measured(v)) => Length::Measured(v),
-            (Length::Auto, Length::Auto) => Length::Auto,
-        }
+        self.checked_add(rhs).expect(TOO_MANY_TERMS)
     }
 }
 
@@ -235,16 +543,93 @@ impl SubAssign<Self> for Length {
     }
 }
 
-impl Debug for Length {
+impl Interpolate for MeasuredLength {
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        self.mul(1.0 - t).add(to.mul(t))
+    }
+}
+
+/// `auto` can't be mixed with measured lengths, it switches at the halfway, so
+/// do expressions of different operators or units.
+impl Interpolate for Length {
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        match (self, to) {
+            (Length::Measured(from), Length::Measured(to)) => {
+                Length::Measured(from.interpolate(to, t))
+            }
+            (Length::Expr(from), Length::Expr(to)) if from.same_shape(to) => {
+                let mut expr = *from;
+                for (value, to) in expr.values.iter_mut().zip(&to.values) {
+                    *value = value.interpolate(to, t);
+                }
+                Length::Expr(expr)
+            }
+            _ => discrete(self, to, t),
+        }
+    }
+}
+
+impl Display for Length {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let length = match self {
-            Length::Auto => return write!(f, "auto"),
-            Length::Measured(l) => l,
-        };
+        match self {
+            Length::Auto => write!(f, "auto"),
+            Length::Measured(m) => Display::fmt(m, f),
+            Length::Expr(expr) => Display::fmt(expr, f),
+        }
+    }
+}
+
+/// Written the way [`FromStr`](std::str::FromStr) of [`Length`] parses back to
+/// the same expression, `max(min(p, max), min)` is written as `clamp()`.
+impl Display for LengthExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        enum Shown {
+            /// Needs parentheses on the right of `+`.
+            Sum(String),
+            Min(String, String),
+            Other(String),
+        }
+
+        impl Shown {
+            fn text(self) -> String {
+                match self {
+                    Shown::Sum(text) | Shown::Other(text) => text,
+                    Shown::Min(lhs, rhs) => format!("min({lhs}, {rhs})"),
+                }
+            }
+        }
+
+        let shown = self.fold(
+            |m| {
+                let text = m.to_string();
+                if m.debug_fields().filter(|&(value, _)| value != 0.0).count() > 1 {
+                    Shown::Sum(text)
+                } else {
+                    Shown::Other(text)
+                }
+            },
+            |op, lhs, rhs| match (op, lhs) {
+                (ExprOp::Min, lhs) => Shown::Min(lhs.text(), rhs.text()),
+                (ExprOp::Max, Shown::Min(preferred, max)) => {
+                    Shown::Other(format!("clamp({}, {preferred}, {max})", rhs.text()))
+                }
+                (ExprOp::Max, lhs) => Shown::Other(format!("max({}, {})", lhs.text(), rhs.text())),
+                (ExprOp::Add, lhs) => Shown::Sum(match rhs {
+                    Shown::Sum(rhs) => format!("{} + ({rhs})", lhs.text()),
+                    rhs => format!("{} + {}", lhs.text(), rhs.text()),
+                }),
+                (ExprOp::Operand, _) => unreachable!(),
+            },
+        );
+        write!(f, "{}", shown.text())
+    }
+}
 
+impl Display for MeasuredLength {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut is_first = true;
 
-        for (value, unit) in length.debug_fields() {
+        for (value, unit) in self.debug_fields() {
             if value == 0.0 {
                 continue;
             }
@@ -262,6 +647,18 @@ impl Debug for Length {
     }
 }
 
+impl Debug for Length {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Debug for MeasuredLength {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
 fn fmt_value(out_buf: &mut Formatter, value: f32, unit: &str) -> std::fmt::Result {
     let mut byte_buf = [0u8; 32];
     let mut fmt_buf = BufWriter::new(&mut byte_buf as &mut [u8]);
@@ -272,3 +669,86 @@ fn fmt_value(out_buf: &mut Formatter, value: f32, unit: &str) -> std::fmt::Resul
         .trim_end_matches('.');
     write!(out_buf, "{s}{unit}")
 }
+
+#[test]
+fn test_resolve() {
+    let standard = LengthStandard {
+        global: LengthStandardGlobalPart {
+            dpi: 2.0,
+            viewport_size: Size {
+                width: 800,
+                height: 600,
+            },
+            root_font_size: 16.0,
+        },
+        percentage_reference: 100.0,
+        font_size: 20.0,
+    };
+    let resolve = |length| standard.resolve(length);
+
+    assert_eq!(resolve(EM + REM + PX), Some(20.0 + 32.0 + 2.0));
+    assert_eq!(resolve((0.5 * PCT).min(2 * EM)), Some(40.0));
+    assert_eq!(resolve((0.5 * PCT).max(2 * EM)), Some(50.0));
+    assert_eq!(resolve((10 * PX).clamp(EM, 2 * REM)), Some(20.0));
+    // the minimum wins if the limits overlap
+    assert_eq!(resolve(PCT.clamp(4 * EM, REM)), Some(80.0));
+    assert_eq!(resolve(-(0.5 * PCT).min(2 * EM)), Some(-40.0));
+    assert_eq!(resolve((0.5 * PCT).min(2 * EM) + 10 * PX), Some(60.0));
+    assert_eq!(resolve(Length::Auto.min(PX)), None);
+    assert_eq!(
+        format!("{:?}", (0.5 * PCT).clamp(EM, 2 * REM)),
+        "clamp(1em, 0.5pct, 2rem)"
+    );
+}
+
+#[test]
+fn test_resolve_nested() {
+    let standard = LengthStandard {
+        global: LengthStandardGlobalPart {
+            dpi: 2.0,
+            viewport_size: Size {
+                width: 800,
+                height: 600,
+            },
+            root_font_size: 16.0,
+        },
+        percentage_reference: 100.0,
+        font_size: 20.0,
+    };
+    let resolve = |length| standard.resolve(length);
+
+    // every operand of a chain still limits
+    assert_eq!(resolve(PCT.min(2 * EM).min(30 * PX)), Some(40.0));
+    assert_eq!(resolve(PCT.min(30 * PX).min(2 * EM)), Some(40.0));
+    assert_eq!(resolve(PCT.max(2 * EM).max(60 * PX)), Some(120.0));
+
+    // and so does every operand nested inside
+    assert_eq!(resolve(PCT.min((10 * PX).max(2 * EM))), Some(40.0));
+    assert_eq!(resolve(PCT.min((30 * PX).max(2 * EM))), Some(60.0));
+    assert_eq!(resolve(-PCT.min((30 * PX).max(2 * EM))), Some(-60.0));
+    assert_eq!(resolve((10 * PX).max(EM.min(REM))), Some(20.0));
+
+    // both sides of a sum are kept
+    assert_eq!(resolve(PCT.min(EM) + (2 * EM).max(REM)), Some(60.0));
+    assert_eq!(resolve(PCT.min(EM) - (2 * EM).max(REM)), Some(-20.0));
+
+    assert_eq!(
+        format!("{:?}", PCT.min(2 * EM).min(30 * PX)),
+        "min(min(1pct, 2em), 30px)"
+    );
+    assert_eq!(
+        format!("{:?}", PCT.min((30 * PX).max(2 * EM))),
+        "min(1pct, max(30px, 2em))"
+    );
+    assert_eq!(
+        format!("{:?}", PCT.min(EM) + (PX + VW)),
+        "min(1pct, 1em) + (1px + 1vw)"
+    );
+
+    let mut sum = PCT.min(EM);
+    for _ in 0..5 {
+        sum = sum.checked_add(PX.max(EM)).unwrap();
+    }
+    assert_eq!(resolve(sum), Some(20.0 + 5.0 * 20.0));
+    assert_eq!(sum.checked_add(PX.max(EM)), None);
+}
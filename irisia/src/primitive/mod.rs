@@ -1,16 +1,20 @@
 pub use self::{
-    corner::Corner, length::Length, point::Point, rect::Rect, region::Region, size::Size,
+    color::ColorValue, corner::Corner, length::Length, parse::ParseError, point::Point, rect::Rect,
+    region::Region, size::Size, transform::Transform,
 };
 
 #[macro_use]
 mod mul_dimensions;
 
+pub mod color;
 pub mod corner;
 pub mod length;
 pub mod line;
+pub mod parse;
 pub mod point;
 pub mod rect;
 pub mod region;
 pub mod size;
+pub mod transform;
 
 pub type Result<T> = anyhow::Result<T>;
@@ -83,6 +83,17 @@ macro_rules! impl_mul_dimensions {
             }
         }
 
+        impl<T> crate::animation::Interpolate for $Type<T>
+        where
+            T: crate::animation::Interpolate,
+        {
+            fn interpolate(&self, to: &Self, t: f32) -> Self {
+                $Type {
+                    $($dim: self.$dim.interpolate(&to.$dim, t),)*
+                }
+            }
+        }
+
         impl<T> std::ops::Neg for $Type<T>
         where
             T: std::ops::Neg,
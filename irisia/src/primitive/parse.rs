@@ -0,0 +1,366 @@
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use super::length::{EM, Length, PCT, PX, REM, VH, VMAX, VMIN, VW};
+
+/// Error of parsing a value from a string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// Byte offset in the string where the error is found.
+    pub position: usize,
+    pub message: &'static str,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at byte {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Reads tokens from a string, whitespaces between them are skipped.
+pub(crate) struct Cursor<'a> {
+    source: &'a str,
+    position: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            position: 0,
+        }
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn error_at(&self, position: usize, message: &'static str) -> ParseError {
+        ParseError { position, message }
+    }
+
+    pub fn error(&self, message: &'static str) -> ParseError {
+        self.error_at(self.position, message)
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.source[self.position..]
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    /// Where the next token starts.
+    pub fn token_start(&mut self) -> usize {
+        self.skip_whitespace();
+        self.position
+    }
+
+    /// Consume `token` if the rest starts with it, ignoring ascii case.
+    pub fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        match self.rest().get(..token.len()) {
+            Some(head) if head.eq_ignore_ascii_case(token) => {
+                self.position += token.len();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn expect(&mut self, token: &str, message: &'static str) -> Result<(), ParseError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error(message))
+        }
+    }
+
+    /// Consume ascii letters, and `%` if it's the first.
+    pub fn ident(&mut self) -> &'a str {
+        let rest = self.rest();
+        let len = if rest.starts_with('%') {
+            1
+        } else {
+            rest.find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(rest.len())
+        };
+        self.position += len;
+        &rest[..len]
+    }
+
+    /// Consume ascii hex digits.
+    pub fn hex_digits(&mut self) -> &'a str {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !c.is_ascii_hexdigit())
+            .unwrap_or(rest.len());
+        self.position += len;
+        &rest[..len]
+    }
+
+    /// An unsigned decimal number, the exponent is only taken if digits follow
+    /// so that `2em` is a number with a unit.
+    pub fn number(&mut self) -> Result<f32, ParseError> {
+        self.skip_whitespace();
+        let rest = self.rest().as_bytes();
+        let digits = |from: usize| {
+            rest[from..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count()
+        };
+
+        let mut len = digits(0);
+        if rest.get(len) == Some(&b'.') && digits(len + 1) > 0 {
+            len += 1 + digits(len + 1);
+        }
+        if len == 0 {
+            return Err(self.error("expected a number"));
+        }
+        if let Some(b'e' | b'E') = rest.get(len) {
+            let sign = matches!(rest.get(len + 1), Some(b'+' | b'-')) as usize;
+            let exponent = digits(len + 1 + sign);
+            if exponent > 0 {
+                len += 1 + sign + exponent;
+            }
+        }
+
+        let number = self.rest()[..len]
+            .parse()
+            .map_err(|_| self.error("invalid number"))?;
+        self.position += len;
+        Ok(number)
+    }
+
+    /// Succeeds if nothing but whitespaces is left.
+    pub fn finish(&mut self) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        if self.rest().is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("unexpected character"))
+        }
+    }
+}
+
+/// Parses what [`Display`] of [`Length`] writes, and css lengths like `12px`,
+/// `50%`, `2rem` and `calc(100% - 2rem)`. `pct` is a hundred percents like
+/// `PCT`. Once `min()`, `max()` or `clamp()` is involved, there can be at most
+/// [`MAX_TERMS`](super::length::LengthExpr::MAX_TERMS) terms.
+impl FromStr for Length {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cursor = Cursor::new(s);
+        if cursor.eat("auto") {
+            cursor.finish()?;
+            return Ok(Length::Auto);
+        }
+
+        let start = cursor.token_start();
+        let length = sum(&mut cursor)?.into_length(&cursor, start)?;
+        cursor.finish()?;
+        Ok(length)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Value {
+    Number(f32),
+    Length(Length),
+}
+
+impl Value {
+    /// A unitless zero is a length too.
+    fn into_length(self, cursor: &Cursor, start: usize) -> Result<Length, ParseError> {
+        match self {
+            Value::Length(length) => Ok(length),
+            Value::Number(0.0) => Ok(Length::ZERO),
+            Value::Number(_) => Err(cursor.error_at(start, "expected a length with a unit")),
+        }
+    }
+}
+
+fn sum(cursor: &mut Cursor) -> Result<Value, ParseError> {
+    let start = cursor.token_start();
+    let mut value = product(cursor)?;
+
+    loop {
+        let negative = if cursor.eat("+") {
+            false
+        } else if cursor.eat("-") {
+            true
+        } else {
+            return Ok(value);
+        };
+
+        let rhs_start = cursor.token_start();
+        let rhs = match product(cursor)? {
+            Value::Number(number) if negative => Value::Number(-number),
+            Value::Length(length) if negative => Value::Length(-length),
+            rhs => rhs,
+        };
+
+        value = match (value, rhs) {
+            (Value::Number(lhs), Value::Number(rhs)) => Value::Number(lhs + rhs),
+            (lhs, rhs) => Value::Length(
+                lhs.into_length(cursor, start)?
+                    .checked_add(rhs.into_length(cursor, rhs_start)?)
+                    .ok_or_else(|| cursor.error_at(start, TOO_MANY_TERMS))?,
+            ),
+        };
+    }
+}
+
+fn product(cursor: &mut Cursor) -> Result<Value, ParseError> {
+    let mut value = factor(cursor)?;
+
+    loop {
+        let divide = if cursor.eat("*") {
+            false
+        } else if cursor.eat("/") {
+            true
+        } else {
+            return Ok(value);
+        };
+
+        let rhs_start = cursor.token_start();
+        value = match (value, factor(cursor)?, divide) {
+            (_, Value::Number(0.0), true) => {
+                return Err(cursor.error_at(rhs_start, "division by zero"));
+            }
+            (_, Value::Length(_), true) => {
+                return Err(cursor.error_at(rhs_start, "can't divide by a length"));
+            }
+            (Value::Number(lhs), Value::Number(rhs), false) => Value::Number(lhs * rhs),
+            (Value::Number(lhs), Value::Number(rhs), true) => Value::Number(lhs / rhs),
+            (Value::Length(lhs), Value::Number(rhs), false)
+            | (Value::Number(rhs), Value::Length(lhs), false) => Value::Length(lhs * rhs),
+            (Value::Length(lhs), Value::Number(rhs), true) => Value::Length(lhs / rhs),
+            (Value::Length(_), Value::Length(_), false) => {
+                return Err(cursor.error_at(rhs_start, "can't multiply two lengths"));
+            }
+        };
+    }
+}
+
+const TOO_MANY_TERMS: &str = "too many terms in `min()`, `max()` or `clamp()`";
+
+fn factor(cursor: &mut Cursor) -> Result<Value, ParseError> {
+    if cursor.eat("-") {
+        return Ok(match factor(cursor)? {
+            Value::Number(number) => Value::Number(-number),
+            Value::Length(length) => Value::Length(-length),
+        });
+    }
+    if cursor.eat("+") {
+        return factor(cursor);
+    }
+
+    if cursor.eat("(") || cursor.eat("calc(") {
+        let value = sum(cursor)?;
+        cursor.expect(")", "expected `)`")?;
+        return Ok(value);
+    }
+
+    // argument count and how they are combined
+    type Function = fn(Length, &[Length]) -> Option<Length>;
+    let start = cursor.token_start();
+    let function: Option<(usize, Function)> = if cursor.eat("min(") {
+        Some((2, |x, args| x.checked_min(args[0])))
+    } else if cursor.eat("max(") {
+        Some((2, |x, args| x.checked_max(args[0])))
+    } else if cursor.eat("clamp(") {
+        // `clamp(min, preferred, max)` in css
+        Some((3, |min, args| args[0].checked_clamp(min, args[1])))
+    } else {
+        None
+    };
+
+    if let Some((count, function)) = function {
+        let mut args = Vec::with_capacity(count);
+        for index in 0..count {
+            if index != 0 {
+                cursor.expect(",", "expected `,`")?;
+            }
+            let arg_start = cursor.token_start();
+            args.push(sum(cursor)?.into_length(cursor, arg_start)?);
+        }
+        cursor.expect(")", "expected `)`")?;
+        return function(args[0], &args[1..])
+            .map(Value::Length)
+            .ok_or_else(|| cursor.error_at(start, TOO_MANY_TERMS));
+    }
+
+    let number = cursor.number()?;
+    let unit_start = cursor.position();
+    let (number, unit) = match cursor.ident().to_ascii_lowercase().as_str() {
+        "" => return Ok(Value::Number(number)),
+        "px" => (number, PX),
+        "vw" => (number, VW),
+        "vh" => (number, VH),
+        "vmin" => (number, VMIN),
+        "vmax" => (number, VMAX),
+        "pct" => (number, PCT),
+        "%" => (number / 100.0, PCT),
+        "em" => (number, EM),
+        "rem" => (number, REM),
+        _ => return Err(cursor.error_at(unit_start, "unknown unit")),
+    };
+    Ok(Value::Length(number * unit))
+}
+
+#[test]
+fn test_parse_length() {
+    let parse = |s: &str| s.parse::<Length>();
+
+    assert_eq!(parse("auto").unwrap(), Length::Auto);
+    assert_eq!(parse("12px").unwrap(), 12 * PX);
+    assert_eq!(parse("50%").unwrap(), 0.5 * PCT);
+    assert_eq!(parse("0").unwrap(), Length::ZERO);
+    assert_eq!(parse("calc(100% - 2rem)").unwrap(), PCT - 2 * REM);
+    assert_eq!(parse("(1px + 2px) * 3 / 2").unwrap(), 4.5 * PX);
+    assert_eq!(
+        parse("clamp(10px, 50vw, 20em)").unwrap(),
+        (50 * VW).clamp(10 * PX, 20 * EM)
+    );
+
+    for length in [
+        10 * PX - 0.5 * PCT,
+        (2 * EM).min(3 * REM + VMIN),
+        VW.max(VMAX).clamp(Length::ZERO, 100 * PX),
+        PCT.min(2 * EM).min(30 * PX),
+        PCT.min((30 * PX).max(2 * EM)) - 4 * PX,
+        PCT.min(EM) + (2 * EM).max(REM) + (PX + VW),
+        -(EM.clamp(PX, VW)),
+        Length::ZERO,
+        Length::Auto,
+    ] {
+        assert_eq!(parse(&length.to_string()).unwrap(), length);
+    }
+
+    assert_eq!(parse("12").unwrap_err().position, 0);
+    assert_eq!(parse("1px + 2furlong").unwrap_err().position, 7);
+    assert_eq!(parse("1px / 0").unwrap_err().position, 6);
+    assert_eq!(
+        parse("min(1pct, max(2px, 3px))").unwrap(),
+        PCT.min((2 * PX).max(3 * PX))
+    );
+    assert_eq!(
+        parse(concat!(
+            "min(1px + 1vw + 1vh + 1em, 1pct + 1rem + 1vmin + 1vmax)",
+            " + max(1px + 1vw + 1vh, 1pct + 1em)"
+        ))
+        .unwrap_err()
+        .position,
+        0
+    );
+    assert_eq!(parse("calc(1px").unwrap_err().position, 8);
+}
@@ -0,0 +1,81 @@
+use irisia_backend::skia_safe::Matrix;
+
+use crate::animation::Interpolate;
+
+use super::{
+    Point,
+    length::{Length, LengthStandard},
+};
+
+/// 2D transform of an element. Applied around the transform origin in the
+/// order of scale, rotate and then translate.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Transform {
+    /// Percentages refer to the size of the element itself.
+    pub translate: Point<Length>,
+    pub scale: Point<f32>,
+    /// Clockwise, in degrees.
+    pub rotate: f32,
+}
+
+impl Transform {
+    pub const IDENTITY: Self = Self {
+        translate: Point::all(Length::ZERO),
+        scale: Point::all(1.0),
+        rotate: 0.0,
+    };
+
+    pub const fn translate(mut self, x: Length, y: Length) -> Self {
+        self.translate = Point { x, y };
+        self
+    }
+
+    pub const fn scale(mut self, x: f32, y: f32) -> Self {
+        self.scale = Point { x, y };
+        self
+    }
+
+    pub const fn rotate(mut self, degrees: f32) -> Self {
+        self.rotate = degrees;
+        self
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == Self::IDENTITY
+    }
+
+    /// `origin` and the result are relative to the left-top of the element,
+    /// `length_standard` takes the element size as the percentage reference.
+    pub(crate) fn to_matrix(
+        &self,
+        origin: Point<f32>,
+        length_standard: Point<LengthStandard>,
+    ) -> Matrix {
+        let translate = self
+            .translate
+            .map_with(length_standard, |len, std| std.resolve(len).unwrap_or(0.0));
+
+        let mut matrix = Matrix::translate((origin.x + translate.x, origin.y + translate.y));
+        matrix
+            .pre_rotate(self.rotate, None)
+            .pre_scale((self.scale.x, self.scale.y), None)
+            .pre_translate((-origin.x, -origin.y));
+        matrix
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Interpolate for Transform {
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        Self {
+            translate: self.translate.interpolate(&to.translate, t),
+            scale: self.scale.interpolate(&to.scale, t),
+            rotate: self.rotate.interpolate(&to.rotate, t),
+        }
+    }
+}